@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sudoku_solver::solver::backtracking::BacktrackingSolver;
+use sudoku_solver::solver::grader::Grader;
+use sudoku_solver::solver::SudokuSolver;
+use sudoku_solver::sudoku::candidates::Candidates;
+use sudoku_solver::sudoku::grid::SudokuGrid;
+use sudoku_solver::sudoku::house::House;
+
+fn load_grids() -> Vec<SudokuGrid> {
+    ["grid6", "grid7", "grid8", "grid9", "grid10", "grid11"]
+        .iter()
+        .map(|name| SudokuGrid::try_from(PathBuf::from(format!("data/{name}.txt"))).unwrap())
+        .collect()
+}
+
+fn solve_logically(c: &mut Criterion) {
+    let grids = load_grids();
+    let solver = SudokuSolver::new().with_brute_force_fallback(false);
+
+    c.bench_function("solve data grids logically", |b| {
+        b.iter(|| {
+            for grid in grids.iter() {
+                black_box(solver.solve(black_box(grid)));
+            }
+        })
+    });
+}
+
+fn solve_with_backtracking(c: &mut Criterion) {
+    let grids = load_grids();
+    let solver = BacktrackingSolver::new();
+
+    c.bench_function("solve data grids with backtracking", |b| {
+        b.iter(|| {
+            for grid in grids.iter() {
+                black_box(solver.solve(black_box(grid)));
+            }
+        })
+    });
+}
+
+fn grade(c: &mut Criterion) {
+    let grids = load_grids();
+    let grader = Grader::new();
+
+    c.bench_function("grade data grids", |b| {
+        b.iter(|| {
+            for grid in grids.iter() {
+                black_box(grader.grade(black_box(grid)));
+            }
+        })
+    });
+}
+
+/// The candidates of the empty fields of every house, after a few logical steps so they are not
+/// all full.
+fn load_house_candidates() -> Vec<Vec<Candidates>> {
+    let solver = SudokuSolver::new().with_brute_force_fallback(false);
+
+    load_grids()
+        .into_iter()
+        .flat_map(|mut grid| {
+            for _ in 0..10 {
+                let deductions = solver.determine_deductions(&grid);
+                grid.apply_deductions(&deductions);
+            }
+
+            House::all()
+                .map(|house| {
+                    grid.get_fields_in_house(house)
+                        .into_iter()
+                        .filter(|f| !f.is_filled())
+                        .map(|f| f.possibilities())
+                        .collect()
+                })
+                .collect::<Vec<Vec<Candidates>>>()
+        })
+        .collect()
+}
+
+/// Looks for naked pairs the way the strategies did while candidates were a `Vec<usize>`.
+fn count_naked_pairs_with_vecs(houses: &[Vec<Vec<usize>>]) -> usize {
+    let mut pairs = 0;
+    for fields in houses {
+        for (i, a) in fields.iter().enumerate() {
+            for b in fields[i + 1..].iter() {
+                let mut union = a.clone();
+                union.extend(b.iter().filter(|p| !a.contains(p)));
+                union.sort();
+                union.dedup();
+
+                if union.len() == 2 && fields.iter().any(|f| f.iter().any(|p| !union.contains(p))) {
+                    pairs += 1;
+                }
+            }
+        }
+    }
+    pairs
+}
+
+fn count_naked_pairs_with_candidates(houses: &[Vec<Candidates>]) -> usize {
+    let mut pairs = 0;
+    for fields in houses {
+        for (i, a) in fields.iter().enumerate() {
+            for b in fields[i + 1..].iter() {
+                let union = *a | *b;
+
+                if union.len() == 2 && fields.iter().any(|f| !f.is_subset(&union)) {
+                    pairs += 1;
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// Compares the bitset candidates with the `Vec<usize>` they replaced on the same work.
+fn candidate_sets(c: &mut Criterion) {
+    let houses = load_house_candidates();
+    let vec_houses = houses
+        .iter()
+        .map(|fields| fields.iter().map(|f| f.iter().collect()).collect())
+        .collect::<Vec<Vec<Vec<usize>>>>();
+    assert_eq!(
+        count_naked_pairs_with_vecs(&vec_houses),
+        count_naked_pairs_with_candidates(&houses)
+    );
+
+    let mut group = c.benchmark_group("find naked pairs");
+    group.bench_function("Vec<usize>", |b| {
+        b.iter(|| count_naked_pairs_with_vecs(black_box(&vec_houses)))
+    });
+    group.bench_function("Candidates", |b| {
+        b.iter(|| count_naked_pairs_with_candidates(black_box(&houses)))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    solve_logically,
+    solve_with_backtracking,
+    grade,
+    candidate_sets
+);
+criterion_main!(benches);
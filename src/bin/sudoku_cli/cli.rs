@@ -0,0 +1,201 @@
+use std::path::PathBuf;
+
+use sudoku_solver::generator::{GeneratorOptions, PuzzleGenerator};
+use sudoku_solver::solver::backtracking::BacktrackingSolver;
+use sudoku_solver::solver::grader::Grader;
+use sudoku_solver::solver::{SolutionCount, SudokuSolver};
+use sudoku_solver::sudoku::grid::SudokuGrid;
+use sudoku_solver::sudoku::validation::Validity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    Solved = 0,
+    Stuck = 1,
+    InvalidInput = 2,
+    MultipleSolutions = 3,
+}
+
+impl ExitStatus {
+    pub fn code(&self) -> i32 {
+        *self as i32
+    }
+}
+
+fn load_grid(path: Option<&String>, usage: &str) -> Result<SudokuGrid, ExitStatus> {
+    let path = match path {
+        None => {
+            eprintln!("Usage: {usage}");
+            return Err(ExitStatus::InvalidInput);
+        }
+        Some(path) => PathBuf::from(path),
+    };
+
+    let grid = SudokuGrid::try_from(path).map_err(|e| {
+        eprintln!("{e}");
+        ExitStatus::InvalidInput
+    })?;
+
+    let conflicts = grid.find_conflicts();
+    if !conflicts.duplicates.is_empty() {
+        for (a, b) in conflicts.duplicates.iter() {
+            eprintln!("{a} and {b} hold the same value");
+        }
+        return Err(ExitStatus::InvalidInput);
+    }
+
+    Ok(grid)
+}
+
+/// Whether the uniqueness strategies may be used. They assume the puzzle has a single solution
+/// and make wrong eliminations on any other puzzle, so they are only used on unique ones.
+fn use_uniqueness_strategies(grid: &SudokuGrid, args: &[String]) -> bool {
+    !args.iter().any(|a| a == "--no-uniqueness")
+        && SudokuSolver::new().count_solutions(grid) == SolutionCount::Unique
+}
+
+pub fn solve(args: Vec<String>) -> ExitStatus {
+    let logic_only = args.iter().any(|a| a == "--logic-only");
+    let path = args.iter().find(|a| !a.starts_with("--"));
+    let usage = "sudoku_cli solve <path> [--logic-only] [--no-uniqueness]";
+    let grid = match load_grid(path, usage) {
+        Ok(grid) => grid,
+        Err(status) => return status,
+    };
+
+    let grid = SudokuSolver::new()
+        .with_brute_force_fallback(false)
+        .with_uniqueness_strategies(use_uniqueness_strategies(&grid, &args))
+        .solve(&grid);
+
+    match grid.validate() {
+        Validity::Solved => {
+            println!("{grid}");
+            println!("Solved");
+            return ExitStatus::Solved;
+        }
+        Validity::CompleteWithErrors => {
+            println!("{grid}");
+            println!("Puzzle has no solution");
+            return ExitStatus::InvalidInput;
+        }
+        Validity::Incomplete => (),
+    }
+
+    if logic_only {
+        println!("{grid}");
+        println!("Stuck, no further progress with logic only");
+        return ExitStatus::Stuck;
+    }
+
+    match BacktrackingSolver::new().solve(&grid) {
+        Some(solution) => {
+            println!("{}", solution.grid());
+            println!("Solved with {} guesses", solution.guesses());
+            ExitStatus::Solved
+        }
+        None => {
+            println!("{grid}");
+            println!("Puzzle has no solution");
+            ExitStatus::InvalidInput
+        }
+    }
+}
+
+pub fn count(args: Vec<String>) -> ExitStatus {
+    let usage = "sudoku_cli count <path> [--limit <n>]";
+    let mut path = None;
+    let mut limit = 2;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--limit" => match args.next().and_then(|l| l.parse::<usize>().ok()) {
+                Some(l) if l > 1 => limit = l,
+                _ => {
+                    eprintln!("Usage: {usage}");
+                    return ExitStatus::InvalidInput;
+                }
+            },
+            _ => path = Some(arg),
+        }
+    }
+
+    let grid = match load_grid(path, usage) {
+        Ok(grid) => grid,
+        Err(status) => return status,
+    };
+
+    let solutions = SudokuSolver::new().find_solutions(&grid, limit);
+    for solution in solutions.iter() {
+        println!("{solution}\n");
+    }
+
+    println!("Found {} solution(s) (limit {limit})", solutions.len());
+
+    let solution_count = SolutionCount::from(solutions.len());
+    println!("{solution_count}");
+
+    match solution_count {
+        SolutionCount::None => ExitStatus::InvalidInput,
+        SolutionCount::Unique => ExitStatus::Solved,
+        SolutionCount::Multiple => ExitStatus::MultipleSolutions,
+    }
+}
+
+pub fn generate(args: Vec<String>) -> ExitStatus {
+    let usage =
+        "sudoku_cli generate [--clues <n>] [--symmetry <symmetry>] [--difficulty <difficulty>]";
+    let mut options = GeneratorOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = args.next();
+        let parsed = match (arg.as_str(), value) {
+            ("--clues", Some(v)) => v.parse().map(|c| options.clues = c).is_ok(),
+            ("--symmetry", Some(v)) => v.parse().map(|s| options.symmetry = s).is_ok(),
+            ("--difficulty", Some(v)) => v.parse().map(|d| options.difficulty = Some(d)).is_ok(),
+            _ => false,
+        };
+
+        if !parsed {
+            eprintln!("Usage: {usage}");
+            return ExitStatus::InvalidInput;
+        }
+    }
+
+    match PuzzleGenerator::new(options).generate() {
+        Some(grid) => {
+            println!("{grid}");
+            ExitStatus::Solved
+        }
+        None => {
+            eprintln!("Could not generate a puzzle with these options");
+            ExitStatus::Stuck
+        }
+    }
+}
+
+pub fn grade(args: Vec<String>) -> ExitStatus {
+    let path = args.iter().find(|a| !a.starts_with("--"));
+    let grid = match load_grid(path, "sudoku_cli grade <path> [--no-uniqueness]") {
+        Ok(grid) => grid,
+        Err(status) => return status,
+    };
+
+    let solver =
+        SudokuSolver::new().with_uniqueness_strategies(use_uniqueness_strategies(&grid, &args));
+    match Grader::with_solver(solver).grade(&grid) {
+        Some(grading) => {
+            for technique in grading.techniques() {
+                println!(
+                    "{:.1} {} x{}",
+                    technique.rating, technique.name, technique.count
+                );
+            }
+            println!("Rating {grading}");
+            ExitStatus::Solved
+        }
+        None => {
+            println!("Puzzle has no solution");
+            ExitStatus::InvalidInput
+        }
+    }
+}
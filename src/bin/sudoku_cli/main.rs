@@ -0,0 +1,24 @@
+//! The command line interface. It is a console program of its own, as the Windows release build
+//! of the UI has no console to print to or return an exit code in.
+
+use std::env;
+use std::process;
+
+mod cli;
+
+fn main() {
+    let mut args = env::args().skip(1);
+
+    let status = match args.next().as_deref() {
+        Some("solve") => cli::solve(args.collect()),
+        Some("count") => cli::count(args.collect()),
+        Some("generate") => cli::generate(args.collect()),
+        Some("grade") => cli::grade(args.collect()),
+        _ => {
+            eprintln!("Usage: sudoku_cli <solve|count|generate|grade> [options]");
+            cli::ExitStatus::InvalidInput
+        }
+    };
+
+    process::exit(status.code());
+}
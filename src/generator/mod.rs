@@ -0,0 +1,233 @@
+use std::{fmt, str::FromStr};
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::solver::backtracking::BacktrackingSolver;
+use crate::solver::grader::{Difficulty, Grader};
+use crate::sudoku::grid::SudokuGrid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Symmetry {
+    #[default]
+    None,
+    Rotational,
+    Horizontal,
+    Vertical,
+    Diagonal,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 5] = [
+        Symmetry::None,
+        Symmetry::Rotational,
+        Symmetry::Horizontal,
+        Symmetry::Vertical,
+        Symmetry::Diagonal,
+    ];
+
+    /// All positions that have to be cleared together with the given one to keep the symmetry.
+    fn orbit(&self, row: usize, column: usize) -> Vec<(usize, usize)> {
+        let mirrored = match self {
+            Symmetry::None => (row, column),
+            Symmetry::Rotational => (8 - row, 8 - column),
+            Symmetry::Horizontal => (8 - row, column),
+            Symmetry::Vertical => (row, 8 - column),
+            Symmetry::Diagonal => (column, row),
+        };
+
+        if mirrored == (row, column) {
+            vec![(row, column)]
+        } else {
+            vec![(row, column), mirrored]
+        }
+    }
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Symmetry::None => write!(f, "none"),
+            Symmetry::Rotational => write!(f, "rotational"),
+            Symmetry::Horizontal => write!(f, "horizontal"),
+            Symmetry::Vertical => write!(f, "vertical"),
+            Symmetry::Diagonal => write!(f, "diagonal"),
+        }
+    }
+}
+
+impl FromStr for Symmetry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Symmetry::ALL
+            .into_iter()
+            .find(|symmetry| symmetry.to_string() == s)
+            .ok_or(format!("Unknown symmetry {s}"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneratorOptions {
+    pub symmetry: Symmetry,
+    /// Givens are only removed as long as at least this many remain.
+    pub clues: usize,
+    /// `None` accepts any difficulty.
+    pub difficulty: Option<Difficulty>,
+    /// How many full grids are tried before giving up on the target difficulty.
+    pub max_attempts: usize,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self {
+            symmetry: Symmetry::Rotational,
+            clues: 24,
+            difficulty: None,
+            max_attempts: 50,
+        }
+    }
+}
+
+pub struct PuzzleGenerator {
+    options: GeneratorOptions,
+    grader: Grader,
+}
+
+impl PuzzleGenerator {
+    pub fn new(options: GeneratorOptions) -> Self {
+        Self {
+            options,
+            grader: Grader::new(),
+        }
+    }
+
+    pub fn generate(&self) -> Option<SudokuGrid> {
+        self.generate_with_rng(&mut rand::thread_rng())
+    }
+
+    pub fn generate_with_rng<R: Rng>(&self, rng: &mut R) -> Option<SudokuGrid> {
+        for _ in 0..self.options.max_attempts {
+            let solution = Self::full_grid(rng)?;
+            let puzzle = self.remove_givens(solution, rng);
+
+            match self.options.difficulty {
+                Some(difficulty) if self.difficulty(&puzzle) != Some(difficulty) => {}
+                _ => return Some(puzzle),
+            }
+        }
+
+        None
+    }
+
+    fn difficulty(&self, grid: &SudokuGrid) -> Option<Difficulty> {
+        self.grader.grade(grid).map(|grading| grading.difficulty())
+    }
+
+    /// Fills the three independent boxes on the diagonal randomly and lets the backtracking
+    /// solver complete the rest.
+    fn full_grid<R: Rng>(rng: &mut R) -> Option<[[usize; 9]; 9]> {
+        let mut values = [[0; 9]; 9];
+        for box_idx in 0..3 {
+            let mut digits = (1..=9).collect::<Vec<usize>>();
+            digits.shuffle(rng);
+
+            for (i, digit) in digits.into_iter().enumerate() {
+                values[box_idx * 3 + i / 3][box_idx * 3 + i % 3] = digit;
+            }
+        }
+
+        let solution = BacktrackingSolver::new().solve(&SudokuGrid::from(values))?;
+        for (row_idx, row) in solution.grid().rows().iter().enumerate() {
+            for (col_idx, field) in row.iter().enumerate() {
+                values[row_idx][col_idx] = field.value()?;
+            }
+        }
+
+        Some(values)
+    }
+
+    fn remove_givens<R: Rng>(&self, mut values: [[usize; 9]; 9], rng: &mut R) -> SudokuGrid {
+        let mut positions = (0..81)
+            .map(|i| (i / 9, i % 9))
+            .collect::<Vec<(usize, usize)>>();
+        positions.shuffle(rng);
+
+        let mut clues = 81;
+        for (row, column) in positions {
+            if values[row][column] == 0 {
+                continue;
+            }
+
+            let orbit = self.options.symmetry.orbit(row, column);
+            if clues < self.options.clues + orbit.len() {
+                continue;
+            }
+
+            let previous = orbit
+                .iter()
+                .map(|(r, c)| std::mem::take(&mut values[*r][*c]))
+                .collect::<Vec<usize>>();
+
+            let grid = SudokuGrid::from(values);
+            let is_unique = BacktrackingSolver::new().find_solutions(&grid, 2).len() == 1;
+
+            if is_unique
+                && self.options.difficulty.is_none_or(|difficulty| {
+                    difficulty == Difficulty::Expert
+                        || self.difficulty(&grid).is_some_and(|d| d <= difficulty)
+                })
+            {
+                clues -= orbit.len();
+            } else {
+                for ((r, c), value) in orbit.into_iter().zip(previous) {
+                    values[r][c] = value;
+                }
+            }
+        }
+
+        SudokuGrid::from(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::{GeneratorOptions, PuzzleGenerator, Symmetry};
+    use crate::solver::grader::{Difficulty, Grader};
+    use crate::solver::{SolutionCount, SudokuSolver};
+
+    #[test]
+    fn it_generates_unique_symmetric_puzzles() {
+        let options = GeneratorOptions {
+            symmetry: Symmetry::Rotational,
+            clues: 30,
+            difficulty: Some(Difficulty::Easy),
+            max_attempts: 5,
+        };
+
+        let grid = PuzzleGenerator::new(options)
+            .generate_with_rng(&mut StdRng::seed_from_u64(7))
+            .unwrap();
+
+        assert_eq!(
+            SolutionCount::Unique,
+            SudokuSolver::new().count_solutions(&grid)
+        );
+        assert_eq!(
+            Some(Difficulty::Easy),
+            Grader::new().grade(&grid).map(|g| g.difficulty())
+        );
+
+        let clues = grid.fields().iter().filter(|f| f.is_filled()).count();
+        assert!((30..=40).contains(&clues));
+
+        for field in grid.fields() {
+            let (row, column) = (field.position().row(), field.position().column());
+            let mirrored = grid.rows()[8 - row][8 - column].is_filled();
+            assert_eq!(field.is_filled(), mirrored);
+        }
+    }
+}
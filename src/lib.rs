@@ -0,0 +1,4 @@
+pub mod generator;
+pub mod solver;
+pub mod sudoku;
+pub mod ui;
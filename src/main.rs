@@ -6,27 +6,32 @@ use std::{
 };
 
 use eframe::egui;
-use sudoku::grid::SudokuGrid;
-use ui::SudokuUi;
-
-mod solver;
-mod sudoku;
-mod ui;
+use sudoku_solver::sudoku::grid::SudokuGrid;
+use sudoku_solver::ui::SudokuUi;
 
 fn main() -> Result<(), eframe::Error> {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
+
+    // solving from the command line is up to the sudoku_cli binary
+    let mut error = None;
+    let grid = match env::args().nth(1).as_deref() {
+        None => None,
+        Some(path) => SudokuGrid::try_from(PathBuf::from(path))
+            .map_err(|e| {
+                eprintln!("{path}: {e}");
+                error = Some(format!("Could not open {path}: {e}"));
+            })
+            .ok(),
+    };
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([1080.0, 720.0]),
         ..Default::default()
     };
 
-    let mut args = env::args();
-
-    let grid = match args.nth(1) {
-        None => None,
-        Some(path) => SudokuGrid::try_from(PathBuf::from(path)).ok(),
-    };
-
-    let sudoku_ui = SudokuUi::new(grid);
+    let mut sudoku_ui = SudokuUi::new(grid);
+    if let Some(error) = error {
+        sudoku_ui.show_error(error);
+    }
     eframe::run_native("Sudoku solver", options, Box::new(|_| Box::new(sudoku_ui)))
 }
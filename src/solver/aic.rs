@@ -0,0 +1,423 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::sudoku::candidates::Candidates;
+use crate::sudoku::field::FieldPosition;
+use crate::sudoku::grid::SudokuGrid;
+use crate::sudoku::house::House;
+
+use super::chain::{ChainNode, Link, LinkKind};
+use super::strategy::Strategy;
+use super::{Deduction, SolveStep};
+
+pub const X_CYCLES: &str = "x-cycles";
+pub const AIC: &str = "aic";
+
+/// Longest chain, counted in candidates, the default strategies look for.
+pub const DEFAULT_MAX_CHAIN_LENGTH: usize = 12;
+
+/// Searches chains of candidates whose links alternate between strong and weak. A chain that
+/// starts and ends with a strong link proves that one of its ends is true, so every candidate
+/// that is weakly linked to both ends is false. A chain that leads back to its start through a
+/// strong link proves the start is true. Restricted to a single digit these are X-Cycles.
+pub struct AlternatingInferenceChains {
+    max_length: usize,
+    single_digit: bool,
+}
+
+impl AlternatingInferenceChains {
+    pub fn new(max_length: usize) -> Self {
+        Self {
+            max_length,
+            single_digit: false,
+        }
+    }
+
+    pub fn x_cycles(max_length: usize) -> Self {
+        Self {
+            max_length,
+            single_digit: true,
+        }
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+impl Strategy for AlternatingInferenceChains {
+    fn id(&self) -> &'static str {
+        match self.single_digit {
+            true => X_CYCLES,
+            false => AIC,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self.single_digit {
+            true => "X-Cycles",
+            false => "Alternating inference chain",
+        }
+    }
+
+    fn difficulty(&self) -> f32 {
+        match self.single_digit {
+            true => 6.6,
+            false => 7.0,
+        }
+    }
+
+    fn find_deductions(&self, grid: &SudokuGrid) -> Vec<Deduction> {
+        LinkGraph::new(grid, self.single_digit).find_deductions(self.max_length)
+    }
+}
+
+/// What a chain proves.
+enum Conclusion {
+    /// The listed candidates are false.
+    Eliminations(Vec<usize>),
+    /// The start of the chain is true.
+    Placement,
+}
+
+/// Strong and weak links between all candidates. Candidates are numbered `cell * 9 + digit - 1`.
+struct LinkGraph {
+    candidates: Vec<Candidates>,
+    peers: Vec<Vec<usize>>,
+    strong_links: Vec<Vec<usize>>,
+    single_digit: bool,
+}
+
+impl LinkGraph {
+    fn new(grid: &SudokuGrid, single_digit: bool) -> Self {
+        let positions = (0..81).map(position_of_cell).collect::<Vec<_>>();
+        let peers = positions
+            .iter()
+            .map(|p| (0..81).filter(|o| positions[*o].sees(p)).collect())
+            .collect::<Vec<Vec<usize>>>();
+
+        // placed values are not always removed from the candidates of their peers yet
+        let candidates = positions
+            .iter()
+            .map(|position| match grid.get_field(position.clone()) {
+                Some(field) if !field.is_filled() => grid
+                    .get_peers(position)
+                    .iter()
+                    .filter_map(|f| f.value())
+                    .fold(field.possibilities(), |c, v| c - Candidates::single(v)),
+                _ => Candidates::empty(),
+            })
+            .collect::<Vec<Candidates>>();
+
+        let mut graph = Self {
+            candidates,
+            peers,
+            strong_links: vec![vec![]; 81 * 9],
+            single_digit,
+        };
+
+        if !single_digit {
+            for cell in 0..81 {
+                if let [x, y] = graph.candidates[cell].iter().collect::<Vec<_>>()[..] {
+                    graph.add_strong_link(node(cell, x), node(cell, y));
+                }
+            }
+        }
+
+        for house in House::all() {
+            let cells = house
+                .positions()
+                .iter()
+                .map(|p| p.row() * 9 + p.column())
+                .collect::<Vec<usize>>();
+
+            for digit in 1..=9 {
+                let with_digit = cells
+                    .iter()
+                    .filter(|c| graph.candidates[**c].contains(digit))
+                    .collect::<Vec<_>>();
+
+                if let [a, b] = with_digit[..] {
+                    graph.add_strong_link(node(*a, digit), node(*b, digit));
+                }
+            }
+        }
+
+        graph
+    }
+
+    fn add_strong_link(&mut self, a: usize, b: usize) {
+        if !self.strong_links[a].contains(&b) {
+            self.strong_links[a].push(b);
+            self.strong_links[b].push(a);
+        }
+    }
+
+    fn is_candidate(&self, node: usize) -> bool {
+        self.candidates[node / 9].contains(digit_of(node))
+    }
+
+    /// Candidates of which at most one can be true together with the given one.
+    fn weak_links(&self, node: usize) -> Vec<usize> {
+        let (cell, digit) = (node / 9, digit_of(node));
+        let mut links = self.peers[cell]
+            .iter()
+            .filter(|peer| self.candidates[**peer].contains(digit))
+            .map(|peer| self::node(*peer, digit))
+            .collect::<Vec<usize>>();
+
+        if !self.single_digit {
+            links.extend(
+                self.candidates[cell]
+                    .iter()
+                    .filter(|d| *d != digit)
+                    .map(|d| self::node(cell, d)),
+            );
+        }
+
+        links
+    }
+
+    fn is_weakly_linked(&self, a: usize, b: usize) -> bool {
+        let same_cell = a / 9 == b / 9 && a != b;
+        let same_digit = digit_of(a) == digit_of(b) && self.peers[a / 9].contains(&(b / 9));
+
+        same_digit || (same_cell && !self.single_digit)
+    }
+
+    fn find_deductions(&self, max_length: usize) -> Vec<Deduction> {
+        let mut chains = vec![];
+        for start in (0..81 * 9).filter(|n| self.is_candidate(*n)) {
+            if !self.strong_links[start].is_empty() {
+                chains.append(&mut self.find_chains(start, max_length));
+            }
+        }
+
+        // the shortest chain for every conclusion is enough
+        chains.sort_by_key(|(chain, _)| chain.len());
+        let mut concluded = vec![];
+        let mut deductions = vec![];
+
+        for (chain, conclusion) in chains {
+            let start = chain[0].0;
+            let nodes = match conclusion {
+                Conclusion::Placement => vec![start],
+                Conclusion::Eliminations(eliminations) => eliminations,
+            };
+            let nodes = nodes
+                .into_iter()
+                .filter(|n| !concluded.contains(n))
+                .collect::<Vec<usize>>();
+
+            if nodes.is_empty() {
+                continue;
+            }
+
+            concluded.extend(nodes.iter().copied());
+            deductions.push(self.deduction(&chain, nodes));
+        }
+
+        deductions
+    }
+
+    /// Runs a breadth first search over the chains starting at `start` with a strong link.
+    /// Nodes are paired with whether they were reached through a strong link.
+    fn find_chains(
+        &self,
+        start: usize,
+        max_length: usize,
+    ) -> Vec<(Vec<(usize, bool)>, Conclusion)> {
+        let mut chains = vec![];
+        let mut parents: HashMap<(usize, bool), (usize, bool)> = HashMap::new();
+        let mut queue = VecDeque::from([((start, false), 1)]);
+
+        while let Some(((current, reached_strongly), length)) = queue.pop_front() {
+            if length >= max_length {
+                continue;
+            }
+
+            let next_nodes = match reached_strongly {
+                false => self.strong_links[current].clone(),
+                true => self.weak_links(current),
+            };
+
+            for next in next_nodes {
+                let state = (next, !reached_strongly);
+                if state == (start, false) || parents.contains_key(&state) {
+                    continue;
+                }
+
+                parents.insert(state, (current, reached_strongly));
+                queue.push_back((state, length + 1));
+
+                if !state.1 {
+                    continue;
+                }
+
+                let conclusion = if next == start {
+                    Conclusion::Placement
+                } else {
+                    let eliminations = self
+                        .weak_links(start)
+                        .into_iter()
+                        .filter(|n| *n != next && self.is_weakly_linked(*n, next))
+                        .collect::<Vec<usize>>();
+
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+                    Conclusion::Eliminations(eliminations)
+                };
+
+                let mut chain = vec![state];
+                while let Some(parent) = parents.get(chain.last().unwrap()) {
+                    chain.push(*parent);
+                }
+                chain.reverse();
+
+                chains.push((chain, conclusion));
+            }
+        }
+
+        chains
+    }
+
+    fn deduction(&self, chain: &[(usize, bool)], nodes: Vec<usize>) -> Deduction {
+        let chain_nodes = chain
+            .iter()
+            .map(|(n, _)| ChainNode::new(position_of_cell(n / 9), digit_of(*n)))
+            .collect::<Vec<ChainNode>>();
+        let links = chain
+            .windows(2)
+            .zip(chain_nodes.windows(2))
+            .map(|(states, nodes)| match states[1].1 {
+                true => Link::strong(nodes[0].clone(), nodes[1].clone()),
+                false => Link::weak(nodes[0].clone(), nodes[1].clone()),
+            })
+            .collect::<Vec<Link>>();
+
+        let mut text = chain_nodes[0].to_string();
+        for link in links.iter() {
+            let symbol = match link.kind {
+                LinkKind::Strong => "=",
+                LinkKind::Weak => "-",
+            };
+            text.push_str(&format!(" {symbol} {}", link.to));
+        }
+
+        let justification = chain_nodes
+            .iter()
+            .map(|n| (n.position.clone(), Candidates::single(n.digit)))
+            .collect();
+
+        let start = &chain_nodes[0];
+        let deduction = if chain.first().map(|(n, _)| n) == chain.last().map(|(n, _)| n) {
+            Deduction::set_value(start.position.clone(), start.digit, justification)
+                .with_explanation(format!(
+                    "{text}: if {start} were false, the chain would make it true"
+                ))
+        } else {
+            let end = &chain_nodes[chain_nodes.len() - 1];
+            let solve_steps = nodes
+                .iter()
+                .map(|n| {
+                    (
+                        position_of_cell(n / 9),
+                        SolveStep::RemovePossibilities(Candidates::single(digit_of(*n))),
+                    )
+                })
+                .collect();
+
+            Deduction::new(solve_steps, justification).with_explanation(format!(
+                "{text}: {start} or {end} is true, so no candidate linked to both can be"
+            ))
+        };
+
+        deduction.with_links(links)
+    }
+}
+
+fn node(cell: usize, digit: usize) -> usize {
+    cell * 9 + digit - 1
+}
+
+fn digit_of(node: usize) -> usize {
+    node % 9 + 1
+}
+
+fn position_of_cell(cell: usize) -> FieldPosition {
+    FieldPosition::new(cell / 9, cell % 9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AlternatingInferenceChains;
+    use crate::solver::strategy::Strategy;
+    use crate::solver::SolveStep;
+    use crate::sudoku::candidates::Candidates;
+    use crate::sudoku::field::FieldPosition;
+    use crate::sudoku::grid::SudokuGrid;
+
+    #[test]
+    fn it_finds_chains_through_bivalue_fields() {
+        let grid = SudokuGrid::with_possibilities(&[
+            ((0, 0), &[1, 2]),
+            ((0, 5), &[1, 3]),
+            ((4, 0), &[2, 3]),
+        ]);
+
+        let deductions = AlternatingInferenceChains::new(12).find_deductions(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(
+            vec![(
+                FieldPosition::new(4, 5),
+                SolveStep::RemovePossibilities(Candidates::single(3))
+            )],
+            deductions[0].solve_steps
+        );
+        assert_eq!(5, deductions[0].links.len());
+
+        assert!(AlternatingInferenceChains::new(5)
+            .find_deductions(&grid)
+            .is_empty());
+        assert!(AlternatingInferenceChains::x_cycles(12)
+            .find_deductions(&grid)
+            .is_empty());
+    }
+
+    #[test]
+    fn it_finds_x_cycles() {
+        let row_1 = (0..9).filter(|c| ![0, 4].contains(c)).map(|c| ((1, c), 1));
+        let row_5 = (0..9).filter(|c| ![0, 5].contains(c)).map(|c| ((5, c), 1));
+        let mut grid = SudokuGrid::from([[0; 9]; 9]);
+        grid.apply_solve_steps(
+            &row_1
+                .chain(row_5)
+                .map(|((r, c), digit)| {
+                    (
+                        FieldPosition::new(r, c),
+                        SolveStep::RemovePossibilities(Candidates::single(digit)),
+                    )
+                })
+                .collect(),
+        );
+
+        let deductions = AlternatingInferenceChains::x_cycles(12).find_deductions(&grid);
+        let mut removed = deductions
+            .into_iter()
+            .flat_map(|d| d.solve_steps)
+            .map(|(position, _)| position)
+            .collect::<Vec<FieldPosition>>();
+        removed.sort_by_key(|p| (p.row(), p.column()));
+
+        assert_eq!(
+            vec![
+                FieldPosition::new(0, 5),
+                FieldPosition::new(2, 5),
+                FieldPosition::new(3, 4),
+                FieldPosition::new(4, 4)
+            ],
+            removed
+        );
+    }
+}
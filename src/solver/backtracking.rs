@@ -0,0 +1,245 @@
+use crate::sudoku::candidates::Candidates;
+use crate::sudoku::field::FieldPosition;
+use crate::sudoku::grid::SudokuGrid;
+
+use super::SolveStep;
+
+#[derive(Debug, Clone)]
+pub struct Solution {
+    grid: SudokuGrid,
+    guesses: usize,
+}
+
+impl Solution {
+    pub fn grid(&self) -> &SudokuGrid {
+        &self.grid
+    }
+
+    /// Number of placements that had to be made in fields with more than one candidate.
+    pub fn guesses(&self) -> usize {
+        self.guesses
+    }
+}
+
+/// Depth-first search which always continues with the empty field that has the fewest
+/// candidates left. Candidates already removed from a field are respected.
+#[derive(Default)]
+pub struct BacktrackingSolver {}
+
+impl BacktrackingSolver {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn solve(&self, grid: &SudokuGrid) -> Option<Solution> {
+        self.find_solutions(grid, 1).pop()
+    }
+
+    /// Collects up to `limit` distinct solutions, the search stops as soon as the limit is reached.
+    pub fn find_solutions(&self, grid: &SudokuGrid, limit: usize) -> Vec<Solution> {
+        let mut solutions = vec![];
+        let mut board = match Board::try_from(grid) {
+            Ok(board) => board,
+            Err(_) => return solutions,
+        };
+
+        if limit == 0 {
+            return solutions;
+        }
+
+        board.search(&mut 0, &mut |board, guesses| {
+            let mut solved_grid = grid.clone();
+            solved_grid.apply_solve_steps(&board.solve_steps(grid));
+            solutions.push(Solution {
+                grid: solved_grid,
+                guesses,
+            });
+
+            solutions.len() >= limit
+        });
+
+        solutions
+    }
+
+    /// Determines the steps that fill every empty field of the grid with its solution value.
+    pub fn determine_solve_steps(&self, grid: &SudokuGrid) -> Vec<(FieldPosition, SolveStep)> {
+        let mut solve_steps = vec![];
+        if let Ok(mut board) = Board::try_from(grid) {
+            board.search(&mut 0, &mut |board, _| {
+                solve_steps = board.solve_steps(grid);
+                true
+            });
+        }
+
+        solve_steps
+    }
+}
+
+struct Board {
+    values: [usize; 81],
+    allowed: [Candidates; 81],
+    rows: [Candidates; 9],
+    columns: [Candidates; 9],
+    boxes: [Candidates; 9],
+}
+
+impl Board {
+    fn box_id(index: usize) -> usize {
+        (index / 27) * 3 + (index % 9) / 3
+    }
+
+    fn candidates(&self, index: usize) -> Candidates {
+        self.allowed[index]
+            - (self.rows[index / 9] | self.columns[index % 9] | self.boxes[Self::box_id(index)])
+    }
+
+    fn place(&mut self, index: usize, value: usize) {
+        self.values[index] = value;
+        self.rows[index / 9].insert(value);
+        self.columns[index % 9].insert(value);
+        self.boxes[Self::box_id(index)].insert(value);
+    }
+
+    fn unplace(&mut self, index: usize, value: usize) {
+        self.values[index] = 0;
+        self.rows[index / 9].remove(value);
+        self.columns[index % 9].remove(value);
+        self.boxes[Self::box_id(index)].remove(value);
+    }
+
+    fn most_constrained_empty_field(&self) -> Option<(usize, Candidates)> {
+        let mut best: Option<(usize, Candidates)> = None;
+
+        for index in (0..81).filter(|i| self.values[*i] == 0) {
+            let candidates = self.candidates(index);
+            let count = candidates.len();
+            if count <= 1 {
+                return Some((index, candidates));
+            }
+
+            if best.is_none_or(|(_, c)| count < c.len()) {
+                best = Some((index, candidates));
+            }
+        }
+
+        best
+    }
+
+    /// Walks the search tree and hands every solution to `on_solution`, which returns whether the
+    /// search should stop.
+    fn search<F>(&mut self, guesses: &mut usize, on_solution: &mut F) -> bool
+    where
+        F: FnMut(&Board, usize) -> bool,
+    {
+        let (index, candidates) = match self.most_constrained_empty_field() {
+            None => return on_solution(self, *guesses),
+            Some(field) => field,
+        };
+
+        let is_guess = candidates.len() > 1;
+        for value in candidates {
+            if is_guess {
+                *guesses += 1;
+            }
+
+            self.place(index, value);
+            if self.search(guesses, on_solution) {
+                return true;
+            }
+            self.unplace(index, value);
+        }
+
+        false
+    }
+
+    fn solve_steps(&self, grid: &SudokuGrid) -> Vec<(FieldPosition, SolveStep)> {
+        grid.fields()
+            .iter()
+            .filter(|f| !f.is_filled())
+            .map(|f| {
+                let position = f.position();
+                let value = self.values[position.row() * 9 + position.column()];
+                (position.clone(), SolveStep::SetValue(value))
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&SudokuGrid> for Board {
+    type Error = String;
+
+    fn try_from(grid: &SudokuGrid) -> Result<Self, Self::Error> {
+        let mut board = Board {
+            values: [0; 81],
+            allowed: [Candidates::all(); 81],
+            rows: [Candidates::empty(); 9],
+            columns: [Candidates::empty(); 9],
+            boxes: [Candidates::empty(); 9],
+        };
+
+        for field in grid.fields() {
+            let index = field.position().row() * 9 + field.position().column();
+            match field.value() {
+                Some(value) => {
+                    if !board.candidates(index).contains(value) {
+                        return Err(format!(
+                            "{value} appears more than once around {} / {}",
+                            field.position().row(),
+                            field.position().column()
+                        ));
+                    }
+                    board.place(index, value);
+                }
+                None => {
+                    board.allowed[index] = field.possibilities();
+                }
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::BacktrackingSolver;
+    use crate::sudoku::grid::SudokuGrid;
+
+    #[test]
+    fn it_solves_every_valid_grid_in_data() {
+        for name in [
+            "grid6", "grid7", "grid8", "grid9", "grid10", "grid11", "grid13",
+        ] {
+            let path = PathBuf::from(format!("data/{name}.txt"));
+            let grid = SudokuGrid::try_from(path).unwrap();
+
+            let solution = BacktrackingSolver::new().solve(&grid);
+
+            assert!(solution.is_some(), "{name} could not be solved");
+            assert!(solution.unwrap().grid().is_solved());
+        }
+    }
+
+    #[test]
+    fn it_stops_counting_solutions_at_the_limit() {
+        let grid = SudokuGrid::try_from(PathBuf::from("data/grid12.txt")).unwrap();
+
+        assert_eq!(1, BacktrackingSolver::new().find_solutions(&grid, 1).len());
+        assert_eq!(2, BacktrackingSolver::new().find_solutions(&grid, 2).len());
+
+        let grid = SudokuGrid::try_from(PathBuf::from("data/grid9.txt")).unwrap();
+
+        assert_eq!(1, BacktrackingSolver::new().find_solutions(&grid, 2).len());
+    }
+
+    #[test]
+    fn it_rejects_grids_without_solution() {
+        for name in ["grid", "grid2", "grid3"] {
+            let grid = SudokuGrid::try_from(PathBuf::from(format!("data/{name}.txt"))).unwrap();
+
+            assert!(BacktrackingSolver::new().solve(&grid).is_none());
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use super::aic::{self, AlternatingInferenceChains};
+use super::strategies;
+use super::strategy::Strategy;
+use super::SudokuSolver;
+
+/// Assembles a [`SudokuSolver`] from a custom list of strategies.
+pub struct SudokuSolverBuilder {
+    strategies: Vec<Box<dyn Strategy>>,
+    disabled_strategies: Vec<&'static str>,
+    max_difficulty: Option<f32>,
+    max_chain_length: Option<usize>,
+    brute_force_fallback: bool,
+    uniqueness_strategies: bool,
+}
+
+impl SudokuSolverBuilder {
+    pub fn new() -> Self {
+        Self {
+            strategies: vec![],
+            disabled_strategies: vec![],
+            max_difficulty: None,
+            max_chain_length: None,
+            brute_force_fallback: true,
+            uniqueness_strategies: true,
+        }
+    }
+
+    /// Adds all strategies the solver ships with, ordered as `SudokuSolver::new` uses them.
+    pub fn with_default_strategies(mut self) -> Self {
+        self.strategies
+            .append(&mut strategies::default_strategies());
+        self
+    }
+
+    pub fn with_strategy<S: Strategy + 'static>(mut self, strategy: S) -> Self {
+        self.strategies.push(Box::new(strategy));
+        self
+    }
+
+    pub fn without_strategy(mut self, id: &'static str) -> Self {
+        self.disabled_strategies.push(id);
+        self
+    }
+
+    /// Disables every strategy that is rated harder than the given difficulty.
+    pub fn with_max_difficulty(mut self, max_difficulty: f32) -> Self {
+        self.max_difficulty = Some(max_difficulty);
+        self
+    }
+
+    /// Limits how many candidates the X-Cycles and AIC strategies may chain together.
+    pub fn with_max_chain_length(mut self, max_chain_length: usize) -> Self {
+        self.max_chain_length = Some(max_chain_length);
+        self
+    }
+
+    pub fn with_brute_force_fallback(mut self, brute_force_fallback: bool) -> Self {
+        self.brute_force_fallback = brute_force_fallback;
+        self
+    }
+
+    /// Whether strategies that assume the puzzle has a single solution may be used.
+    pub fn with_uniqueness_strategies(mut self, uniqueness_strategies: bool) -> Self {
+        self.uniqueness_strategies = uniqueness_strategies;
+        self
+    }
+
+    pub fn build(mut self) -> SudokuSolver {
+        if let Some(max_chain_length) = self.max_chain_length {
+            for strategy in self.strategies.iter_mut() {
+                match strategy.id() {
+                    aic::X_CYCLES => {
+                        *strategy = Box::new(AlternatingInferenceChains::x_cycles(max_chain_length))
+                    }
+                    aic::AIC => {
+                        *strategy = Box::new(AlternatingInferenceChains::new(max_chain_length))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut disabled_strategies = self.disabled_strategies;
+        if let Some(max_difficulty) = self.max_difficulty {
+            disabled_strategies.extend(
+                self.strategies
+                    .iter()
+                    .filter(|s| s.difficulty() > max_difficulty)
+                    .map(|s| s.id()),
+            );
+        }
+
+        SudokuSolver {
+            solving_strategies: self.strategies,
+            disabled_strategies,
+            brute_force_fallback: self.brute_force_fallback,
+            uniqueness_strategies: self.uniqueness_strategies,
+        }
+    }
+}
+
+impl Default for SudokuSolverBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::sudoku::field::{Field, FieldPosition};
+use crate::sudoku::grid::SudokuGrid;
+use crate::sudoku::house::House;
+
+/// A candidate, i.e. a digit in a field. Chains are made of these.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChainNode {
+    pub position: FieldPosition,
+    pub digit: usize,
+}
+
+impl ChainNode {
+    pub fn new(position: FieldPosition, digit: usize) -> Self {
+        Self { position, digit }
+    }
+}
+
+impl fmt::Display for ChainNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in {}", self.digit, self.position)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    /// At least one of both candidates is true.
+    Strong,
+    /// At most one of both candidates is true.
+    Weak,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    pub from: ChainNode,
+    pub to: ChainNode,
+    pub kind: LinkKind,
+}
+
+impl Link {
+    pub fn strong(from: ChainNode, to: ChainNode) -> Self {
+        Self {
+            from,
+            to,
+            kind: LinkKind::Strong,
+        }
+    }
+
+    pub fn weak(from: ChainNode, to: ChainNode) -> Self {
+        Self {
+            from,
+            to,
+            kind: LinkKind::Weak,
+        }
+    }
+}
+
+/// Two fields which are the only places left for a digit in a house, exactly one of them holds
+/// the digit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConjugatePair {
+    pub house: House,
+    pub positions: [FieldPosition; 2],
+}
+
+impl ConjugatePair {
+    pub fn contains(&self, position: &FieldPosition) -> bool {
+        self.positions.contains(position)
+    }
+
+    /// The other field of the pair.
+    pub fn partner(&self, position: &FieldPosition) -> Option<&FieldPosition> {
+        match &self.positions {
+            [a, b] if a == position => Some(b),
+            [a, b] if b == position => Some(a),
+            _ => None,
+        }
+    }
+}
+
+/// The graph of all conjugate pairs of a single digit. Fields that form a pair in more than one
+/// house are only linked once.
+pub struct ConjugatePairs {
+    digit: usize,
+    pairs: Vec<ConjugatePair>,
+}
+
+impl ConjugatePairs {
+    pub fn new(grid: &SudokuGrid, digit: usize) -> Self {
+        let mut pairs: Vec<ConjugatePair> = vec![];
+
+        for house in House::all() {
+            let Some([a, b]) = conjugate_pair(grid, house, digit) else {
+                continue;
+            };
+
+            let positions = [a.position().clone(), b.position().clone()];
+            if !pairs
+                .iter()
+                .any(|p| p.contains(&positions[0]) && p.contains(&positions[1]))
+            {
+                pairs.push(ConjugatePair { house, positions });
+            }
+        }
+
+        Self { digit, pairs }
+    }
+
+    pub fn digit(&self) -> usize {
+        self.digit
+    }
+
+    pub fn pairs(&self) -> &Vec<ConjugatePair> {
+        &self.pairs
+    }
+
+    /// Splits the graph into its connected parts and colors each of them with two colors, so
+    /// that linked fields never share a color. Parts that can not be colored this way are left
+    /// out.
+    pub fn clusters(&self) -> Vec<Vec<(FieldPosition, bool)>> {
+        let mut clusters = vec![];
+        let mut visited: Vec<FieldPosition> = vec![];
+
+        for pair in self.pairs.iter() {
+            if visited.contains(&pair.positions[0]) {
+                continue;
+            }
+
+            let mut cluster = vec![(pair.positions[0].clone(), true)];
+            let mut consistent = true;
+            let mut next = 0;
+            while let Some((position, color)) = cluster.get(next).cloned() {
+                next += 1;
+                for partner in self.pairs.iter().filter_map(|p| p.partner(&position)) {
+                    match cluster.iter().find(|(p, _)| p == partner) {
+                        Some((_, c)) => consistent &= *c != color,
+                        None => cluster.push((partner.clone(), !color)),
+                    }
+                }
+            }
+
+            visited.extend(cluster.iter().map(|(p, _)| p.clone()));
+            if consistent {
+                clusters.push(cluster);
+            }
+        }
+
+        clusters
+    }
+
+    /// The pairs connecting the given fields as strong links.
+    pub fn links_between(&self, positions: &[FieldPosition]) -> Vec<Link> {
+        self.pairs
+            .iter()
+            .filter(|p| positions.contains(&p.positions[0]) && positions.contains(&p.positions[1]))
+            .map(|p| self.link(p))
+            .collect()
+    }
+
+    pub fn link(&self, pair: &ConjugatePair) -> Link {
+        Link::strong(
+            ChainNode::new(pair.positions[0].clone(), self.digit),
+            ChainNode::new(pair.positions[1].clone(), self.digit),
+        )
+    }
+}
+
+/// The only two fields of the house that can hold the digit, if there are exactly two.
+pub(crate) fn conjugate_pair(grid: &SudokuGrid, house: House, digit: usize) -> Option<[&Field; 2]> {
+    let fields = grid.get_fields_in_house(house);
+    if fields.iter().any(|f| f.value() == Some(digit)) {
+        return None;
+    }
+
+    let fields = fields
+        .into_iter()
+        .filter(|f| !f.is_filled() && f.possibilities().contains(digit))
+        .collect::<Vec<&Field>>();
+
+    fields.try_into().ok()
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+use crate::sudoku::candidates::Candidates;
+use crate::sudoku::field::FieldPosition;
+use crate::sudoku::grid::SudokuGrid;
+use crate::sudoku::house::House;
+
+use super::chain::Link;
+use super::SolveStep;
+
+/// A conclusion drawn by a strategy: the solve steps it allows together with the fields and
+/// candidates that justify them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deduction {
+    /// Name of the strategy, filled in by the solver.
+    pub strategy: &'static str,
+    pub solve_steps: Vec<(FieldPosition, SolveStep)>,
+    pub justification: Vec<(FieldPosition, Candidates)>,
+    /// Why the steps are valid, in words.
+    pub explanation: String,
+    /// Houses the pattern is built from, e.g. the rows of an X-Wing.
+    pub base_houses: Vec<House>,
+    /// Houses the eliminations are made in, e.g. the columns of an X-Wing.
+    pub cover_houses: Vec<House>,
+    /// The chain the deduction follows, so it can be drawn.
+    pub links: Vec<Link>,
+}
+
+impl Deduction {
+    pub fn new(
+        solve_steps: Vec<(FieldPosition, SolveStep)>,
+        justification: Vec<(FieldPosition, Candidates)>,
+    ) -> Self {
+        Self {
+            strategy: "",
+            solve_steps,
+            justification,
+            explanation: String::new(),
+            base_houses: vec![],
+            cover_houses: vec![],
+            links: vec![],
+        }
+    }
+
+    pub fn with_explanation(mut self, explanation: impl Into<String>) -> Self {
+        self.explanation = explanation.into();
+        self
+    }
+
+    pub fn with_houses(mut self, base_houses: Vec<House>, cover_houses: Vec<House>) -> Self {
+        self.base_houses = base_houses;
+        self.cover_houses = cover_houses;
+        self
+    }
+
+    pub fn with_links(mut self, links: Vec<Link>) -> Self {
+        self.links = links;
+        self
+    }
+
+    pub fn set_value(
+        position: FieldPosition,
+        value: usize,
+        justification: Vec<(FieldPosition, Candidates)>,
+    ) -> Self {
+        Self::new(vec![(position, SolveStep::SetValue(value))], justification)
+    }
+
+    /// Drops every step that would not change the grid, e.g. removing a candidate that is already
+    /// gone. Returns whether anything is left.
+    pub fn retain_effective(&mut self, grid: &SudokuGrid) -> bool {
+        self.solve_steps.retain_mut(|(position, solve_step)| {
+            let field = match grid.get_field(position.clone()) {
+                Some(field) if !field.is_filled() => field,
+                _ => return false,
+            };
+
+            match solve_step {
+                SolveStep::SetValue(_) => true,
+                SolveStep::RemovePossibilities(possibilities) => {
+                    *possibilities &= field.possibilities();
+                    !possibilities.is_empty()
+                }
+            }
+        });
+
+        !self.solve_steps.is_empty()
+    }
+
+    /// The solve steps in words, e.g. "remove 3 from 1 / 4 and 2 / 5".
+    pub fn conclusion(&self) -> String {
+        let mut removals: Vec<(Candidates, Vec<String>)> = vec![];
+        let mut values = vec![];
+
+        for (position, solve_step) in self.solve_steps.iter() {
+            match solve_step {
+                SolveStep::SetValue(value) => values.push(format!("set {position} to {value}")),
+                SolveStep::RemovePossibilities(possibilities) => {
+                    match removals.iter_mut().find(|(p, _)| p == possibilities) {
+                        Some((_, positions)) => positions.push(position.to_string()),
+                        None => removals.push((*possibilities, vec![position.to_string()])),
+                    }
+                }
+            }
+        }
+
+        let removals = removals.into_iter().map(|(possibilities, positions)| {
+            format!(
+                "remove {} from {}",
+                join_digits(possibilities),
+                join_words(positions)
+            )
+        });
+
+        values
+            .into_iter()
+            .chain(removals)
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+}
+
+impl fmt::Display for Deduction {
+    /// Renders the deduction as a sentence, e.g. "Hidden single (row): set 2 / 3 to 4, because 4
+    /// fits nowhere else in row 2."
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.strategy, self.conclusion())?;
+        if !self.explanation.is_empty() {
+            write!(f, ", because {}", self.explanation)?;
+        }
+        write!(f, ".")
+    }
+}
+
+/// Lists the digits for the player: "1", "1 and 2", "1, 2 and 3".
+pub(crate) fn join_digits(digits: Candidates) -> String {
+    join_words(digits.iter().map(|d| d.to_string()).collect())
+}
+
+/// Joins the words like a list in a sentence: "a", "a and b", "a, b and c".
+fn join_words(mut words: Vec<String>) -> String {
+    match words.pop() {
+        None => String::new(),
+        Some(last) if words.is_empty() => last,
+        Some(last) => format!("{} and {last}", words.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Deduction;
+    use crate::solver::SolveStep;
+    use crate::sudoku::candidates::Candidates;
+    use crate::sudoku::field::FieldPosition;
+
+    #[test]
+    fn it_renders_a_sentence() {
+        let removal = SolveStep::RemovePossibilities(Candidates::from_iter([3, 4]));
+        let mut deduction = Deduction::new(
+            vec![
+                (FieldPosition::new(1, 4), removal.clone()),
+                (FieldPosition::new(2, 5), removal),
+                (
+                    FieldPosition::new(2, 6),
+                    SolveStep::RemovePossibilities(Candidates::single(7)),
+                ),
+            ],
+            vec![],
+        )
+        .with_explanation("it is an example");
+        deduction.strategy = "Example";
+
+        assert_eq!(
+            "Example: remove 3 and 4 from 1 / 4 and 2 / 5; remove 7 from 2 / 6, because it is an example.",
+            deduction.to_string()
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use std::{fmt, str::FromStr};
+
+use crate::sudoku::grid::SudokuGrid;
+
+use super::backtracking::BacktrackingSolver;
+use super::strategy::Strategy;
+use super::{SudokuSolver, BACKTRACKING};
+
+/// Rating used once the logical techniques stall and the rest has to be guessed.
+pub const BACKTRACKING_RATING: f32 = 10.0;
+
+/// How hard a puzzle is, derived from its rating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    /// Naked and hidden singles are enough.
+    Easy,
+    /// Subsets and other basic techniques are required.
+    Medium,
+    /// Advanced techniques are required.
+    Hard,
+    /// The logical techniques stall, guessing is required.
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+}
+
+impl From<f32> for Difficulty {
+    fn from(rating: f32) -> Self {
+        if rating <= 2.3 {
+            Difficulty::Easy
+        } else if rating <= 4.0 {
+            Difficulty::Medium
+        } else if rating < BACKTRACKING_RATING {
+            Difficulty::Hard
+        } else {
+            Difficulty::Expert
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Medium => write!(f, "medium"),
+            Difficulty::Hard => write!(f, "hard"),
+            Difficulty::Expert => write!(f, "expert"),
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.to_string() == s)
+            .ok_or(format!("Unknown difficulty {s}"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TechniqueUsage {
+    pub name: &'static str,
+    /// Rating on the Sudoku Explainer scale.
+    pub rating: f32,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grading {
+    techniques: Vec<TechniqueUsage>,
+}
+
+impl Grading {
+    /// The rating of the hardest technique that was needed.
+    pub fn rating(&self) -> f32 {
+        self.techniques.iter().map(|t| t.rating).fold(0.0, f32::max)
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::from(self.rating())
+    }
+
+    /// Every technique that was needed, in the order they were first used.
+    pub fn techniques(&self) -> &Vec<TechniqueUsage> {
+        &self.techniques
+    }
+
+    fn record(&mut self, name: &'static str, rating: f32, count: usize) {
+        match self.techniques.iter_mut().find(|t| t.name == name) {
+            Some(usage) => usage.count += count,
+            None => self.techniques.push(TechniqueUsage {
+                name,
+                rating,
+                count,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Grading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1} ({})", self.rating(), self.difficulty())
+    }
+}
+
+/// Solves a puzzle by always applying the simplest strategy that makes progress and rates it by
+/// the hardest one that was needed.
+pub struct Grader {
+    solver: SudokuSolver,
+}
+
+impl Grader {
+    pub fn new() -> Self {
+        Self::with_solver(SudokuSolver::new())
+    }
+
+    /// Grades with the enabled strategies of the given solver, its fallback setting is ignored.
+    pub fn with_solver(solver: SudokuSolver) -> Self {
+        Self { solver }
+    }
+
+    /// Grades the puzzle, returns `None` if it has no solution at all.
+    pub fn grade(&self, grid: &SudokuGrid) -> Option<Grading> {
+        let mut strategies = self
+            .solver
+            .enabled_strategies()
+            .collect::<Vec<&dyn Strategy>>();
+        strategies.sort_by(|a, b| a.difficulty().total_cmp(&b.difficulty()));
+
+        let mut grid = grid.clone();
+        let mut grading = Grading { techniques: vec![] };
+
+        'solve: while !grid.is_solved() {
+            for strategy in strategies.iter() {
+                let deductions = SudokuSolver::determine_deductions_with(*strategy, &grid);
+
+                if !deductions.is_empty() {
+                    grid.apply_deductions(&deductions);
+                    // plain candidate eliminations are not a technique of their own
+                    if strategy.difficulty() > 0.0 {
+                        grading.record(strategy.name(), strategy.difficulty(), deductions.len());
+                    }
+                    continue 'solve;
+                }
+            }
+
+            let solution = BacktrackingSolver::new().solve(&grid)?;
+            grading.record(BACKTRACKING, BACKTRACKING_RATING, solution.guesses());
+            break;
+        }
+
+        Some(grading)
+    }
+}
+
+impl Default for Grader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Difficulty, Grader, TechniqueUsage};
+    use crate::sudoku::grid::SudokuGrid;
+
+    fn grade(name: &str) -> Option<super::Grading> {
+        let grid = SudokuGrid::try_from(PathBuf::from(format!("data/{name}.txt"))).unwrap();
+
+        Grader::new().grade(&grid)
+    }
+
+    #[test]
+    fn it_rates_by_the_hardest_technique() {
+        let grading = grade("grid8").unwrap();
+
+        assert_eq!(
+            vec![
+                "Hidden single (box)",
+                "Hidden single (row)",
+                "Hidden single (column)"
+            ],
+            grading
+                .techniques()
+                .iter()
+                .map(|t| t.name)
+                .collect::<Vec<&str>>()
+        );
+        assert_eq!(1.5, grading.rating());
+        assert_eq!(Difficulty::Easy, grading.difficulty());
+
+        let x_wing = SudokuGrid::parse(
+            "100000569492056108056109240009640801064010000218035604040500016905061402621000005",
+        )
+        .unwrap();
+        let grading = Grader::new().grade(&x_wing).unwrap();
+
+        assert!(grading.techniques().contains(&TechniqueUsage {
+            name: "X-Wing",
+            rating: 3.2,
+            count: 1,
+        }));
+        assert_eq!(3.2, grading.rating());
+        assert_eq!(Difficulty::Medium, grading.difficulty());
+
+        let naked_triple = SudokuGrid::parse(
+            "...4.....4.59....8..96.2..17.61.3.2...........8.2.65.32..8.41..8....16.2.....9...",
+        )
+        .unwrap();
+        let grading = Grader::new().grade(&naked_triple).unwrap();
+
+        assert!(grading.techniques().contains(&TechniqueUsage {
+            name: "Naked triple",
+            rating: 3.6,
+            count: 1,
+        }));
+        assert_eq!(3.6, grading.rating());
+        assert_eq!(Difficulty::Medium, grading.difficulty());
+
+        let grading = grade("grid13").unwrap();
+
+        assert_eq!(Difficulty::Expert, grading.difficulty());
+        assert_eq!(
+            Some(super::BACKTRACKING),
+            grading.techniques().last().map(|t| t.name)
+        );
+    }
+
+    #[test]
+    fn it_does_not_grade_puzzles_without_solution() {
+        assert_eq!(None, grade("grid"));
+    }
+}
@@ -1,78 +1,235 @@
+pub mod aic;
+pub mod backtracking;
+pub mod builder;
+pub mod chain;
+pub mod deduction;
+pub mod grader;
 mod strategies;
-use crate::sudoku::field::{Field, FieldPosition};
+pub mod strategy;
+use std::fmt;
+
+use crate::sudoku::candidates::Candidates;
+use crate::sudoku::field::FieldPosition;
 use crate::sudoku::grid::SudokuGrid;
 
-type SolveFn = dyn Fn(&Field, &SudokuGrid) -> Option<SolveStep>;
+use self::backtracking::BacktrackingSolver;
+use self::builder::SudokuSolverBuilder;
+pub use self::deduction::Deduction;
+use self::strategy::Strategy;
+
+/// Label of the steps that were found by the backtracking fallback.
+pub const BACKTRACKING: &str = "Backtracking";
 
 pub struct SudokuSolver {
-    solving_strategies: Vec<Box<SolveFn>>,
+    solving_strategies: Vec<Box<dyn Strategy>>,
+    disabled_strategies: Vec<&'static str>,
+    brute_force_fallback: bool,
+    uniqueness_strategies: bool,
 }
 
 impl SudokuSolver {
     pub fn new() -> Self {
-        Self {
-            solving_strategies: vec![
-                Box::new(strategies::remove_possibilities_by_row_values),
-                Box::new(strategies::remove_possibilities_by_column_values),
-                Box::new(strategies::remove_possibilities_via_box_values),
-                Box::new(strategies::set_value_if_only_one_possibility_left),
-                Box::new(strategies::set_value_if_field_is_only_owner_of_possibility_in_row),
-                Box::new(strategies::set_value_if_field_is_only_owner_of_possibility_in_column),
-                Box::new(strategies::set_value_if_field_is_only_owner_of_possibility_in_box),
-                Box::new(strategies::remove_possibilities_by_pairs_of_size_nin_row),
-                Box::new(strategies::remove_possibilities_by_pairs_of_size_nin_colummn),
-                Box::new(strategies::remove_possibilities_by_pairs_of_size_nin_box),
-            ],
+        Self::builder().with_default_strategies().build()
+    }
+
+    pub fn builder() -> SudokuSolverBuilder {
+        SudokuSolverBuilder::new()
+    }
+
+    /// A logic-only solver that knows nothing but naked and hidden singles.
+    pub fn singles_only() -> Self {
+        Self::builder()
+            .with_default_strategies()
+            .with_max_difficulty(2.3)
+            .with_brute_force_fallback(false)
+            .build()
+    }
+
+    /// Whether the solver resorts to a backtracking search once no strategy makes progress.
+    pub fn with_brute_force_fallback(mut self, brute_force_fallback: bool) -> Self {
+        self.brute_force_fallback = brute_force_fallback;
+        self
+    }
+
+    /// Whether strategies that assume the puzzle has a single solution may be used.
+    pub fn with_uniqueness_strategies(mut self, uniqueness_strategies: bool) -> Self {
+        self.uniqueness_strategies = uniqueness_strategies;
+        self
+    }
+
+    pub fn uses_uniqueness_strategies(&self) -> bool {
+        self.uniqueness_strategies
+    }
+
+    pub fn set_uniqueness_strategies(&mut self, uniqueness_strategies: bool) {
+        self.uniqueness_strategies = uniqueness_strategies;
+    }
+
+    /// All strategies in the order they are applied, including disabled ones.
+    pub fn strategies(&self) -> impl Iterator<Item = &dyn Strategy> {
+        self.solving_strategies.iter().map(|s| s.as_ref())
+    }
+
+    pub fn enabled_strategies(&self) -> impl Iterator<Item = &dyn Strategy> {
+        self.strategies().filter(|s| {
+            self.is_enabled(s.id()) && (self.uniqueness_strategies || !s.requires_uniqueness())
+        })
+    }
+
+    pub fn is_enabled(&self, id: &str) -> bool {
+        !self.disabled_strategies.contains(&id)
+    }
+
+    pub fn set_enabled(&mut self, id: &'static str, enabled: bool) {
+        self.disabled_strategies.retain(|d| *d != id);
+        if !enabled {
+            self.disabled_strategies.push(id);
         }
     }
 
     pub fn determine_solve_steps(&self, grid: &SudokuGrid) -> Vec<(FieldPosition, SolveStep)> {
-        let mut solve_steps = vec![];
-        for field in grid.fields().iter().filter(|f| !f.is_filled()) {
-            for strategy in self.solving_strategies.iter() {
-                match strategy(field, grid) {
-                    None => {}
-                    Some(SolveStep::SetValue(value)) => {
-                        solve_steps.push((field.position().clone(), SolveStep::SetValue(value)));
-                        break;
-                    }
-                    Some(SolveStep::RemovePossibilities(possibilities_to_remove)) => {
-                        let x = possibilities_to_remove
-                            .iter()
-                            .filter(|p| field.possibilities().contains(p))
-                            .copied()
-                            .collect::<Vec<usize>>();
-                        if !x.is_empty() {
-                            solve_steps.push((
-                                field.position().clone(),
-                                SolveStep::RemovePossibilities(x),
-                            ));
-                        }
-                    }
-                }
+        self.determine_deductions(grid)
+            .into_iter()
+            .flat_map(|deduction| deduction.solve_steps)
+            .collect()
+    }
+
+    /// Returns the deductions of the first enabled strategy that can make progress, falls back
+    /// to a backtracking search if enabled and none of them can.
+    pub fn determine_deductions(&self, grid: &SudokuGrid) -> Vec<Deduction> {
+        let deductions = self.determine_logical_deductions(grid);
+        if !deductions.is_empty() {
+            return deductions;
+        }
+
+        if self.brute_force_fallback && !grid.is_solved() {
+            let solve_steps = BacktrackingSolver::new().determine_solve_steps(grid);
+            if !solve_steps.is_empty() {
+                let mut deduction = Deduction::new(solve_steps, vec![]).with_explanation(
+                    "no strategy makes progress, the values were found by trying out candidates",
+                );
+                deduction.strategy = BACKTRACKING;
+                return vec![deduction];
+            }
+        }
+
+        vec![]
+    }
+
+    /// The single easiest deduction, e.g. to give the player a hint. Never falls back to a
+    /// backtracking search, that would just reveal the solution.
+    pub fn determine_hint(&self, grid: &SudokuGrid) -> Option<Deduction> {
+        self.determine_logical_deductions(grid).into_iter().next()
+    }
+
+    /// Applies solve steps until the solver makes no further progress and returns the resulting grid.
+    pub fn solve(&self, grid: &SudokuGrid) -> SudokuGrid {
+        let mut grid = grid.clone();
+        loop {
+            let solve_steps = self.determine_solve_steps(&grid);
+            if solve_steps.is_empty() {
+                return grid;
             }
+
+            grid.apply_solve_steps(&solve_steps);
+        }
+    }
+
+    /// Returns up to `limit` solutions of the grid, e.g. a limit of 2 is enough to tell whether a
+    /// puzzle is unique.
+    pub fn find_solutions(&self, grid: &SudokuGrid, limit: usize) -> Vec<SudokuGrid> {
+        BacktrackingSolver::new()
+            .find_solutions(grid, limit)
+            .into_iter()
+            .map(|solution| solution.grid().clone())
+            .collect()
+    }
+
+    pub fn count_solutions(&self, grid: &SudokuGrid) -> SolutionCount {
+        SolutionCount::from(self.find_solutions(grid, 2).len())
+    }
+
+    /// The deductions of the first enabled strategy that can make progress.
+    fn determine_logical_deductions(&self, grid: &SudokuGrid) -> Vec<Deduction> {
+        self.enabled_strategies()
+            .map(|strategy| Self::determine_deductions_with(strategy, grid))
+            .find(|deductions| !deductions.is_empty())
+            .unwrap_or_default()
+    }
+
+    fn determine_deductions_with(strategy: &dyn Strategy, grid: &SudokuGrid) -> Vec<Deduction> {
+        strategy
+            .find_deductions(grid)
+            .into_iter()
+            .filter_map(|mut deduction| {
+                deduction.strategy = strategy.name();
+                deduction.retain_effective(grid).then_some(deduction)
+            })
+            .collect()
+    }
+}
+
+impl Default for SudokuSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolutionCount {
+    None,
+    Unique,
+    Multiple,
+}
+
+impl From<usize> for SolutionCount {
+    fn from(count: usize) -> Self {
+        match count {
+            0 => SolutionCount::None,
+            1 => SolutionCount::Unique,
+            _ => SolutionCount::Multiple,
         }
+    }
+}
 
-        solve_steps
+impl fmt::Display for SolutionCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolutionCount::None => write!(f, "No solution"),
+            SolutionCount::Unique => write!(f, "Unique solution"),
+            SolutionCount::Multiple => write!(f, "Multiple solutions"),
+        }
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SolveStep {
     SetValue(usize),
-    RemovePossibilities(Vec<usize>),
+    RemovePossibilities(Candidates),
 }
 
 impl SudokuGrid {
+    pub fn apply_deductions(&mut self, deductions: &[Deduction]) {
+        for deduction in deductions {
+            for (position, solve_step) in deduction.solve_steps.iter() {
+                self.apply_solve_step(position, solve_step);
+            }
+        }
+    }
+
     pub fn apply_solve_steps(&mut self, solve_steps: &Vec<(FieldPosition, SolveStep)>) {
         for (position, solve_step) in solve_steps {
-            if let Some(field) = self.get_field_mut(position) {
-                match &solve_step {
-                    SolveStep::SetValue(value) => field.set_value(*value),
-                    SolveStep::RemovePossibilities(possibilities) => {
-                        for possibiliy in possibilities {
-                            field.remove_possibility(*possibiliy);
-                        }
+            self.apply_solve_step(position, solve_step);
+        }
+    }
+
+    fn apply_solve_step(&mut self, position: &FieldPosition, solve_step: &SolveStep) {
+        if let Some(field) = self.get_field_mut(position) {
+            match &solve_step {
+                SolveStep::SetValue(value) => field.set_value(*value),
+                SolveStep::RemovePossibilities(possibilities) => {
+                    for possibiliy in possibilities.iter() {
+                        field.remove_possibility(possibiliy);
                     }
                 }
             }
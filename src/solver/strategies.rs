@@ -1,252 +1,1693 @@
-use crate::sudoku::field::Field;
+use crate::sudoku::candidates::Candidates;
+use crate::sudoku::field::{Field, FieldPosition};
 use crate::sudoku::grid::SudokuGrid;
+use crate::sudoku::house::House;
 
-use super::SolveStep;
+use super::aic::{AlternatingInferenceChains, DEFAULT_MAX_CHAIN_LENGTH};
+use super::chain::{conjugate_pair, ChainNode, ConjugatePair, ConjugatePairs, Link};
+use super::deduction::join_digits;
+use super::strategy::{FnStrategy, Strategy};
+use super::{Deduction, SolveStep};
 
-pub(crate) fn remove_possibilities_by_row_values(
-    field: &Field,
-    grid: &SudokuGrid,
-) -> Option<SolveStep> {
-    let mut values: Vec<usize> = grid
-        .get_fields_in_row(field.position().row())
-        .map_or(vec![], |fields| {
-            fields.iter().filter_map(|f| f.value()).collect()
-        });
-
-    values.dedup();
-
-    Some(SolveStep::RemovePossibilities(values))
+/// All strategies ordered from the simplest to the hardest.
+pub(crate) fn default_strategies() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(FnStrategy::new(
+            "row-elimination",
+            "Row elimination",
+            0.0,
+            remove_possibilities_by_row_values,
+        )),
+        Box::new(FnStrategy::new(
+            "column-elimination",
+            "Column elimination",
+            0.0,
+            remove_possibilities_by_column_values,
+        )),
+        Box::new(FnStrategy::new(
+            "box-elimination",
+            "Box elimination",
+            0.0,
+            remove_possibilities_via_box_values,
+        )),
+        Box::new(FnStrategy::new(
+            "hidden-single-box",
+            "Hidden single (box)",
+            1.2,
+            set_value_if_field_is_only_owner_of_possibility_in_box,
+        )),
+        Box::new(FnStrategy::new(
+            "hidden-single-row",
+            "Hidden single (row)",
+            1.5,
+            set_value_if_field_is_only_owner_of_possibility_in_row,
+        )),
+        Box::new(FnStrategy::new(
+            "hidden-single-column",
+            "Hidden single (column)",
+            1.5,
+            set_value_if_field_is_only_owner_of_possibility_in_column,
+        )),
+        Box::new(FnStrategy::new(
+            "naked-single",
+            "Naked single",
+            2.3,
+            set_value_if_only_one_possibility_left,
+        )),
+        Box::new(FnStrategy::new(
+            "locked-candidates-pointing",
+            "Locked candidates (pointing)",
+            2.6,
+            remove_possibilities_by_pointing_candidates,
+        )),
+        Box::new(FnStrategy::new(
+            "locked-candidates-claiming",
+            "Locked candidates (claiming)",
+            2.8,
+            remove_possibilities_by_claiming_candidates,
+        )),
+        Box::new(FnStrategy::new(
+            "naked-pair",
+            "Naked pair",
+            3.0,
+            remove_possibilities_by_naked_pairs,
+        )),
+        Box::new(FnStrategy::new(
+            "x-wing",
+            "X-Wing",
+            3.2,
+            remove_possibilities_by_x_wing,
+        )),
+        Box::new(FnStrategy::new(
+            "hidden-pair",
+            "Hidden pair",
+            3.4,
+            remove_possibilities_by_hidden_pairs,
+        )),
+        Box::new(FnStrategy::new(
+            "finned-x-wing",
+            "Finned X-Wing",
+            3.4,
+            remove_possibilities_by_finned_x_wing,
+        )),
+        Box::new(FnStrategy::new(
+            "naked-triple",
+            "Naked triple",
+            3.6,
+            remove_possibilities_by_naked_triples,
+        )),
+        Box::new(FnStrategy::new(
+            "swordfish",
+            "Swordfish",
+            3.8,
+            remove_possibilities_by_swordfish,
+        )),
+        Box::new(FnStrategy::new(
+            "hidden-triple",
+            "Hidden triple",
+            3.8,
+            remove_possibilities_by_hidden_triples,
+        )),
+        Box::new(FnStrategy::new(
+            "finned-swordfish",
+            "Finned Swordfish",
+            4.0,
+            remove_possibilities_by_finned_swordfish,
+        )),
+        Box::new(FnStrategy::new(
+            "skyscraper",
+            "Skyscraper",
+            4.0,
+            remove_possibilities_by_skyscraper,
+        )),
+        Box::new(FnStrategy::new(
+            "two-string-kite",
+            "2-String Kite",
+            4.1,
+            remove_possibilities_by_two_string_kite,
+        )),
+        Box::new(FnStrategy::new(
+            "xy-wing",
+            "XY-Wing",
+            4.2,
+            remove_possibilities_by_xy_wing,
+        )),
+        Box::new(FnStrategy::new(
+            "turbot-fish",
+            "Turbot Fish",
+            4.2,
+            remove_possibilities_by_turbot_fish,
+        )),
+        Box::new(FnStrategy::new(
+            "xyz-wing",
+            "XYZ-Wing",
+            4.4,
+            remove_possibilities_by_xyz_wing,
+        )),
+        Box::new(FnStrategy::new(
+            "w-wing",
+            "W-Wing",
+            4.4,
+            remove_possibilities_by_w_wing,
+        )),
+        Box::new(
+            FnStrategy::new(
+                "unique-rectangle-1",
+                "Unique rectangle (type 1)",
+                4.5,
+                remove_possibilities_by_unique_rectangle_type_1,
+            )
+            .requiring_uniqueness(),
+        ),
+        Box::new(
+            FnStrategy::new(
+                "unique-rectangle-2",
+                "Unique rectangle (type 2)",
+                4.5,
+                remove_possibilities_by_unique_rectangle_type_2,
+            )
+            .requiring_uniqueness(),
+        ),
+        Box::new(
+            FnStrategy::new(
+                "unique-rectangle-3",
+                "Unique rectangle (type 3)",
+                4.5,
+                remove_possibilities_by_unique_rectangle_type_3,
+            )
+            .requiring_uniqueness(),
+        ),
+        Box::new(
+            FnStrategy::new(
+                "unique-rectangle-4",
+                "Unique rectangle (type 4)",
+                4.5,
+                remove_possibilities_by_unique_rectangle_type_4,
+            )
+            .requiring_uniqueness(),
+        ),
+        Box::new(FnStrategy::new(
+            "simple-coloring",
+            "Simple coloring",
+            4.5,
+            remove_possibilities_by_simple_coloring,
+        )),
+        Box::new(FnStrategy::new(
+            "naked-quad",
+            "Naked quad",
+            5.0,
+            remove_possibilities_by_naked_quads,
+        )),
+        Box::new(FnStrategy::new(
+            "jellyfish",
+            "Jellyfish",
+            5.2,
+            remove_possibilities_by_jellyfish,
+        )),
+        Box::new(FnStrategy::new(
+            "hidden-quad",
+            "Hidden quad",
+            5.4,
+            remove_possibilities_by_hidden_quads,
+        )),
+        Box::new(FnStrategy::new(
+            "finned-jellyfish",
+            "Finned Jellyfish",
+            5.4,
+            remove_possibilities_by_finned_jellyfish,
+        )),
+        Box::new(
+            FnStrategy::new(
+                "bug-plus-one",
+                "BUG+1",
+                5.6,
+                set_value_by_bivalue_universal_grave,
+            )
+            .requiring_uniqueness(),
+        ),
+        Box::new(AlternatingInferenceChains::x_cycles(
+            DEFAULT_MAX_CHAIN_LENGTH,
+        )),
+        Box::new(AlternatingInferenceChains::new(DEFAULT_MAX_CHAIN_LENGTH)),
+    ]
 }
 
-pub(crate) fn remove_possibilities_by_column_values(
-    field: &Field,
-    grid: &SudokuGrid,
-) -> Option<SolveStep> {
-    let mut values: Vec<usize> = grid
-        .get_fields_in_column(field.position().column())
-        .iter()
-        .filter_map(|f| f.value())
-        .collect();
+pub(crate) fn remove_possibilities_by_row_values(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_house_values(grid, House::rows())
+}
 
-    values.dedup();
+pub(crate) fn remove_possibilities_by_column_values(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_house_values(grid, House::columns())
+}
 
-    Some(SolveStep::RemovePossibilities(values))
+pub(crate) fn remove_possibilities_via_box_values(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_house_values(grid, House::boxes())
 }
 
-pub(crate) fn remove_possibilities_via_box_values(
-    field: &Field,
+fn remove_possibilities_by_house_values(
     grid: &SudokuGrid,
-) -> Option<SolveStep> {
-    let box_id = SudokuGrid::get_box_id_for_field(field)?;
+    houses: impl Iterator<Item = House>,
+) -> Vec<Deduction> {
+    let mut deductions = vec![];
 
-    let mut values: Vec<usize> = grid
-        .get_fields_in_box(box_id)
-        .iter()
-        .filter_map(|f| f.value())
-        .collect();
+    for house in houses {
+        let fields = grid.get_fields_in_house(house);
+        let values = fields
+            .iter()
+            .filter_map(|f| f.value())
+            .collect::<Candidates>();
 
-    values.dedup();
+        let solve_steps = fields
+            .iter()
+            .filter(|f| !f.is_filled())
+            .filter_map(|f| {
+                let possibilities = f.possibilities() & values;
 
-    Some(SolveStep::RemovePossibilities(values))
-}
+                (!possibilities.is_empty()).then(|| {
+                    (
+                        f.position().clone(),
+                        SolveStep::RemovePossibilities(possibilities),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if solve_steps.is_empty() {
+            continue;
+        }
+
+        let justification = fields
+            .iter()
+            .filter_map(|f| {
+                let value = f.value()?;
+                solve_steps
+                    .iter()
+                    .any(|(_, step)| {
+                        matches!(step, SolveStep::RemovePossibilities(p) if p.contains(value))
+                    })
+                    .then(|| (f.position().clone(), Candidates::single(value)))
+            })
+            .collect();
 
-pub(crate) fn set_value_if_only_one_possibility_left(
-    field: &Field,
-    _: &SudokuGrid,
-) -> Option<SolveStep> {
-    if field.possibilities().len() == 1 {
-        let value = field.possibilities().first()?.to_owned();
-        println!(
-            "{value} is the only possible value for {} / {}",
-            field.position().row(),
-            field.position().column()
+        deductions.push(
+            Deduction::new(solve_steps, justification)
+                .with_explanation(format!(
+                    "{} {} already placed in {house}",
+                    join_digits(values),
+                    if values.len() == 1 { "is" } else { "are" }
+                ))
+                .with_houses(vec![house], vec![]),
         );
-        Some(SolveStep::SetValue(value))
-    } else {
-        None
     }
+
+    deductions
 }
 
-pub(crate) fn count_possibilities_for_fields(fields: Vec<&Field>) -> [usize; 10] {
+pub(crate) fn set_value_if_only_one_possibility_left(grid: &SudokuGrid) -> Vec<Deduction> {
+    grid.fields()
+        .into_iter()
+        .filter(|f| !f.is_filled() && f.possibilities().len() == 1)
+        .filter_map(|field| {
+            let value = field.possibilities().first()?;
+
+            Some(
+                Deduction::set_value(
+                    field.position().clone(),
+                    value,
+                    vec![(field.position().clone(), Candidates::single(value))],
+                )
+                .with_explanation(format!(
+                    "{value} is the only candidate left in {}",
+                    field.position()
+                )),
+            )
+        })
+        .collect()
+}
+
+pub(crate) fn count_possibilities_for_fields(fields: &[&Field]) -> [usize; 10] {
     let mut all_possibilities = [0; 10];
 
-    for field in fields.iter() {
-        for possibility in field.possibilities().iter() {
-            if let Some(p) = all_possibilities.get_mut(*possibility) {
+    for field in fields.iter().filter(|f| !f.is_filled()) {
+        for possibility in field.possibilities() {
+            if let Some(p) = all_possibilities.get_mut(possibility) {
                 *p += 1;
             }
         }
     }
 
+    // values that are already placed can not be the only owner in another field
+    for value in fields.iter().filter_map(|f| f.value()) {
+        if let Some(p) = all_possibilities.get_mut(value) {
+            *p = 0;
+        }
+    }
+
     all_possibilities
 }
 
 pub(crate) fn set_value_if_field_is_only_owner_of_possibility_in_row(
-    field: &Field,
     grid: &SudokuGrid,
-) -> Option<SolveStep> {
-    let fields = grid.get_fields_in_row(field.position().row())?;
-
-    let possibilities = count_possibilities_for_fields(fields.iter().collect());
+) -> Vec<Deduction> {
+    set_value_if_field_is_only_owner_of_possibility_in_houses(grid, House::rows())
+}
 
-    for possibility in field.possibilities().clone().iter() {
-        if let Some(p) = possibilities.get(*possibility) {
-            if *p == 1 {
-                println!("[row] {possibility} was never found, assuming {} / {} is the only place it can go", field.position().row(), field.position().column());
-                return Some(SolveStep::SetValue(*possibility));
-            }
-        }
-    }
+pub(crate) fn set_value_if_field_is_only_owner_of_possibility_in_column(
+    grid: &SudokuGrid,
+) -> Vec<Deduction> {
+    set_value_if_field_is_only_owner_of_possibility_in_houses(grid, House::columns())
+}
 
-    None
+pub(crate) fn set_value_if_field_is_only_owner_of_possibility_in_box(
+    grid: &SudokuGrid,
+) -> Vec<Deduction> {
+    set_value_if_field_is_only_owner_of_possibility_in_houses(grid, House::boxes())
 }
 
-pub(crate) fn set_value_if_field_is_only_owner_of_possibility_in_column(
-    field: &Field,
+fn set_value_if_field_is_only_owner_of_possibility_in_houses(
     grid: &SudokuGrid,
-) -> Option<SolveStep> {
-    let fields = grid.get_fields_in_column(field.position().column());
+    houses: impl Iterator<Item = House>,
+) -> Vec<Deduction> {
+    let mut deductions = vec![];
 
-    let possibilities = count_possibilities_for_fields(fields);
+    for house in houses {
+        let fields = grid.get_fields_in_house(house);
+        let possibilities = count_possibilities_for_fields(&fields);
 
-    for possibility in field.possibilities().clone().iter() {
-        if let Some(p) = possibilities.get(*possibility) {
-            if *p == 1 {
-                println!("[column] {possibility} was never found, assuming {} / {} is the only place it can go", field.position().row(), field.position().column());
-                return Some(SolveStep::SetValue(*possibility));
+        for (possibility, count) in possibilities.iter().enumerate() {
+            if *count != 1 {
+                continue;
+            }
+
+            let field = fields
+                .iter()
+                .find(|f| !f.is_filled() && f.possibilities().contains(possibility));
+
+            if let Some(field) = field {
+                deductions.push(
+                    Deduction::set_value(
+                        field.position().clone(),
+                        possibility,
+                        vec![(field.position().clone(), Candidates::single(possibility))],
+                    )
+                    .with_explanation(format!("{possibility} fits nowhere else in {house}"))
+                    .with_houses(vec![house], vec![]),
+                );
             }
         }
     }
 
-    None
+    deductions
 }
 
-pub(crate) fn set_value_if_field_is_only_owner_of_possibility_in_box(
-    field: &Field,
+/// A digit confined to one row or column within a box can be removed from the rest of that line.
+pub(crate) fn remove_possibilities_by_pointing_candidates(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_locked_candidates(grid, House::boxes(), |position| {
+        vec![House::Row(position.row()), House::Column(position.column())]
+    })
+}
+
+/// A digit confined to one box within a row or column can be removed from the rest of that box.
+pub(crate) fn remove_possibilities_by_claiming_candidates(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_locked_candidates(grid, House::rows().chain(House::columns()), |position| {
+        vec![House::Box(position.box_id())]
+    })
+}
+
+fn remove_locked_candidates(
     grid: &SudokuGrid,
-) -> Option<SolveStep> {
-    let box_id = SudokuGrid::get_box_id_for_field(field)?;
-    let fields = grid.get_fields_in_box(box_id);
-    let possibilities = count_possibilities_for_fields(fields);
-
-    for possibility in field.possibilities().clone().iter() {
-        if let Some(p) = possibilities.get(*possibility) {
-            if *p == 1 {
-                println!("[box] {possibility} was never found, assuming {} / {} is the only place it can go", field.position().row(), field.position().column());
-                return Some(SolveStep::SetValue(*possibility));
+    houses: impl Iterator<Item = House>,
+    intersecting_houses: impl Fn(&FieldPosition) -> Vec<House>,
+) -> Vec<Deduction> {
+    let mut deductions = vec![];
+
+    for house in houses {
+        let fields = grid.get_fields_in_house(house);
+        let values = fields
+            .iter()
+            .filter_map(|f| f.value())
+            .collect::<Candidates>();
+
+        for digit in Candidates::all() - values {
+            let positions = fields
+                .iter()
+                .filter(|f| !f.is_filled() && f.possibilities().contains(digit))
+                .map(|f| f.position())
+                .collect::<Vec<&FieldPosition>>();
+
+            let Some(first) = positions.first() else {
+                continue;
+            };
+
+            for target in intersecting_houses(first) {
+                if !positions.iter().all(|p| target.contains(p)) {
+                    continue;
+                }
+
+                let solve_steps = grid
+                    .get_fields_in_house(target)
+                    .into_iter()
+                    .filter(|f| {
+                        !f.is_filled()
+                            && !house.contains(f.position())
+                            && f.possibilities().contains(digit)
+                    })
+                    .map(|f| {
+                        (
+                            f.position().clone(),
+                            SolveStep::RemovePossibilities(Candidates::single(digit)),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                if solve_steps.is_empty() {
+                    continue;
+                }
+
+                let justification = positions
+                    .iter()
+                    .map(|p| ((*p).clone(), Candidates::single(digit)))
+                    .collect();
+
+                deductions.push(
+                    Deduction::new(solve_steps, justification)
+                        .with_explanation(format!(
+                            "{digit} is confined to {target} within {house}, so it can not be anywhere else in {target}"
+                        ))
+                        .with_houses(vec![house], vec![target]),
+                );
             }
         }
     }
 
-    None
+    deductions
 }
 
-pub(crate) fn remove_possibilities_by_pairs_of_size_nin_row(
-    field: &Field,
-    grid: &SudokuGrid,
-) -> Option<SolveStep> {
-    let fields = grid.get_fields_in_row(field.position().row())?;
+pub(crate) fn remove_possibilities_by_naked_pairs(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_naked_subsets(grid, 2)
+}
 
-    let fields_possibilities = fields
-        .iter()
-        .filter(|f| f.position().column() != field.position().column())
-        .map(|field| field.possibilities().clone())
-        .collect::<Vec<Vec<usize>>>();
+pub(crate) fn remove_possibilities_by_naked_triples(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_naked_subsets(grid, 3)
+}
+
+pub(crate) fn remove_possibilities_by_naked_quads(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_naked_subsets(grid, 4)
+}
+
+fn remove_possibilities_by_naked_subsets(grid: &SudokuGrid, size: usize) -> Vec<Deduction> {
+    let mut deductions = vec![];
+
+    for house in House::all() {
+        let fields = grid
+            .get_fields_in_house(house)
+            .into_iter()
+            .filter(|f| !f.is_filled())
+            .collect::<Vec<&Field>>();
+
+        for field in fields.iter() {
+            let others = fields
+                .iter()
+                .filter(|f| f.position() != field.position())
+                .collect::<Vec<_>>();
+
+            let grouped_possibilities = find_grouped_possibilities(
+                others.iter().map(|f| f.possibilities()).collect(),
+                size,
+            );
 
-    let possibilities_to_remove = find_grouped_possibilities(fields_possibilities);
-    if possibilities_to_remove.is_empty() {
-        None
-    } else {
-        Some(SolveStep::RemovePossibilities(possibilities_to_remove))
+            let possibilities_to_remove = field.possibilities() & grouped_possibilities;
+
+            if possibilities_to_remove.is_empty() {
+                continue;
+            }
+
+            let justification = others
+                .iter()
+                .filter(|f| {
+                    f.possibilities().len() > 1
+                        && f.possibilities().is_subset(&grouped_possibilities)
+                })
+                .map(|f| (f.position().clone(), f.possibilities()))
+                .collect::<Vec<_>>();
+            let subset = justification
+                .iter()
+                .map(|(p, _)| p.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            deductions.push(
+                Deduction::new(
+                    vec![(
+                        field.position().clone(),
+                        SolveStep::RemovePossibilities(possibilities_to_remove),
+                    )],
+                    justification,
+                )
+                .with_explanation(format!(
+                    "{subset} can only hold {} between them, so no other field of {house} can",
+                    join_digits(grouped_possibilities)
+                ))
+                .with_houses(vec![house], vec![]),
+            );
+        }
     }
+
+    deductions
 }
 
-fn find_grouped_possibilities(fields_possibilities: Vec<Vec<usize>>) -> Vec<usize> {
-    let mut possibilties_to_remove = vec![];
+/// The digits of every group of `size` fields which can only hold the same `size` digits between
+/// them.
+fn find_grouped_possibilities(fields_possibilities: Vec<Candidates>, size: usize) -> Candidates {
+    let mut grouped_possibilities = Candidates::empty();
 
     let fields_possibilities = fields_possibilities
         .into_iter()
         .filter(|fp| fp.len() > 1)
-        .collect::<Vec<Vec<usize>>>();
+        .collect::<Vec<Candidates>>();
 
-    for field_possibilities in fields_possibilities.iter() {
-        let len = field_possibilities.len();
+    for field_possibilities in fields_possibilities.iter().filter(|fp| fp.len() == size) {
+        // fields with more possibilities than the one we are looking for are no subset of it
         let matches = fields_possibilities
             .iter()
-            .filter(|possibilities| {
-                // more possibilities than the one we are looking for => ignore this field
-                if possibilities.len() > len {
-                    return false;
+            .filter(|possibilities| possibilities.is_subset(field_possibilities))
+            .count();
+
+        if matches == field_possibilities.len() {
+            grouped_possibilities |= *field_possibilities;
+        }
+    }
+
+    grouped_possibilities
+}
+
+pub(crate) fn remove_possibilities_by_hidden_pairs(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_hidden_subsets(grid, 2)
+}
+
+pub(crate) fn remove_possibilities_by_hidden_triples(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_hidden_subsets(grid, 3)
+}
+
+pub(crate) fn remove_possibilities_by_hidden_quads(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_hidden_subsets(grid, 4)
+}
+
+fn remove_possibilities_by_hidden_subsets(grid: &SudokuGrid, size: usize) -> Vec<Deduction> {
+    let mut deductions = vec![];
+
+    for house in House::all() {
+        let fields = grid.get_fields_in_house(house);
+        let values = fields
+            .iter()
+            .filter_map(|f| f.value())
+            .collect::<Candidates>();
+        let fields_possibilities = fields
+            .iter()
+            .map(|f| match f.is_filled() {
+                true => Candidates::empty(),
+                false => f.possibilities() - values,
+            })
+            .collect();
+
+        for (digits, indexes) in find_hidden_subsets(fields_possibilities, size) {
+            let subset_fields = indexes.iter().map(|i| fields[*i]).collect::<Vec<&Field>>();
+
+            let solve_steps = subset_fields
+                .iter()
+                .filter_map(|f| {
+                    let possibilities = f.possibilities() - digits;
+                    (!possibilities.is_empty()).then(|| {
+                        (
+                            f.position().clone(),
+                            SolveStep::RemovePossibilities(possibilities),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            if solve_steps.is_empty() {
+                continue;
+            }
+
+            let justification = subset_fields
+                .iter()
+                .map(|f| (f.position().clone(), f.possibilities() & digits))
+                .collect();
+
+            deductions.push(
+                Deduction::new(solve_steps, justification)
+                    .with_explanation(format!(
+                        "{} can only go into {size} fields of {house}, so those fields can not hold anything else",
+                        join_digits(digits)
+                    ))
+                    .with_houses(vec![house], vec![]),
+            );
+        }
+    }
+
+    deductions
+}
+
+/// Finds `size` digits which together appear in exactly `size` of the given fields. Returns the
+/// digits together with the indexes of those fields.
+fn find_hidden_subsets(
+    fields_possibilities: Vec<Candidates>,
+    size: usize,
+) -> Vec<(Candidates, Vec<usize>)> {
+    let digits = (1..=9)
+        .filter(|digit| {
+            let count = fields_possibilities
+                .iter()
+                .filter(|p| p.contains(*digit))
+                .count();
+            (1..=size).contains(&count)
+        })
+        .collect::<Vec<usize>>();
+
+    combinations(&digits, size)
+        .into_iter()
+        .filter_map(|combination| {
+            let digits = combination.into_iter().collect::<Candidates>();
+            let indexes = fields_possibilities
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| !(**p & digits).is_empty())
+                .map(|(i, _)| i)
+                .collect::<Vec<usize>>();
+
+            (indexes.len() == size).then_some((digits, indexes))
+        })
+        .collect()
+}
+
+pub(crate) fn remove_possibilities_by_x_wing(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_fish(grid, 2, false)
+}
+
+pub(crate) fn remove_possibilities_by_swordfish(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_fish(grid, 3, false)
+}
+
+pub(crate) fn remove_possibilities_by_jellyfish(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_fish(grid, 4, false)
+}
+
+pub(crate) fn remove_possibilities_by_finned_x_wing(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_fish(grid, 2, true)
+}
+
+pub(crate) fn remove_possibilities_by_finned_swordfish(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_fish(grid, 3, true)
+}
+
+pub(crate) fn remove_possibilities_by_finned_jellyfish(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_fish(grid, 4, true)
+}
+
+/// When a digit is confined to `size` cover lines within `size` parallel base lines, each cover
+/// line gets its digit from one of the base lines and it can be removed from the rest of the
+/// cover lines. A finned fish has extra candidates (the fins) in one box outside the cover lines,
+/// only the cover fields in that box are safe to remove then. A sashimi fish is a finned fish
+/// with a base line that has a single candidate left in the cover lines.
+fn remove_possibilities_by_fish(grid: &SudokuGrid, size: usize, finned: bool) -> Vec<Deduction> {
+    let mut deductions = vec![];
+    for digit in 1..=9 {
+        for lines in [
+            House::rows().collect::<Vec<House>>(),
+            House::columns().collect(),
+        ] {
+            let candidate_lines = lines
+                .iter()
+                .filter_map(|line| {
+                    let fields = grid.get_fields_in_house(*line);
+                    if fields.iter().any(|f| f.value() == Some(digit)) {
+                        return None;
+                    }
+
+                    let positions = fields
+                        .iter()
+                        .filter(|f| !f.is_filled() && f.possibilities().contains(digit))
+                        .map(|f| f.position().clone())
+                        .collect::<Vec<FieldPosition>>();
+
+                    (!positions.is_empty()).then_some((*line, positions))
+                })
+                .collect::<Vec<(House, Vec<FieldPosition>)>>();
+
+            let indexes = (0..candidate_lines.len()).collect::<Vec<usize>>();
+            for combination in combinations(&indexes, size) {
+                let base_houses = combination
+                    .iter()
+                    .map(|i| candidate_lines[*i].0)
+                    .collect::<Vec<House>>();
+                let positions = combination
+                    .iter()
+                    .flat_map(|i| candidate_lines[*i].1.iter())
+                    .collect::<Vec<&FieldPosition>>();
+
+                let mut lines_with_digit = vec![];
+                for line in positions.iter().map(|p| crossing_line(&base_houses[0], p)) {
+                    if !lines_with_digit.contains(&line) {
+                        lines_with_digit.push(line);
+                    }
+                }
+
+                if !finned {
+                    if lines_with_digit.len() != size {
+                        continue;
+                    }
+
+                    let solve_steps =
+                        fish_eliminations(grid, digit, &base_houses, &lines_with_digit, None);
+                    if solve_steps.is_empty() {
+                        continue;
+                    }
+
+                    deductions.push(
+                        Deduction::new(solve_steps, fish_justification(digit, &positions))
+                            .with_explanation(format!(
+                                "{digit} can only go into {} within {}, so it can not be anywhere else in those lines",
+                                join_houses(&lines_with_digit),
+                                join_houses(&base_houses)
+                            ))
+                            .with_houses(base_houses, lines_with_digit),
+                    );
+                    continue;
+                }
+
+                // fins have to share a box, so they add at most three lines
+                if lines_with_digit.len() <= size || lines_with_digit.len() > size + 3 {
+                    continue;
+                }
+
+                for cover_houses in combinations(&lines_with_digit, size) {
+                    let fins = positions
+                        .iter()
+                        .filter(|p| !cover_houses.iter().any(|c| c.contains(p)))
+                        .collect::<Vec<_>>();
+                    let fin_box = fins[0].box_id();
+                    if fins.iter().any(|f| f.box_id() != fin_box) {
+                        continue;
+                    }
+
+                    let body_counts = base_houses
+                        .iter()
+                        .map(|base| {
+                            positions
+                                .iter()
+                                .filter(|p| base.contains(p))
+                                .filter(|p| cover_houses.iter().any(|c| c.contains(p)))
+                                .count()
+                        })
+                        .collect::<Vec<usize>>();
+                    if body_counts.contains(&0) {
+                        continue;
+                    }
+
+                    let solve_steps =
+                        fish_eliminations(grid, digit, &base_houses, &cover_houses, Some(fin_box));
+                    if solve_steps.is_empty() {
+                        continue;
+                    }
+
+                    let kind = match body_counts.contains(&1) {
+                        true => "sashimi",
+                        false => "finned",
+                    };
+
+                    deductions.push(
+                        Deduction::new(solve_steps, fish_justification(digit, &positions))
+                            .with_explanation(format!(
+                                "{digit} can only go into {} within {} or the {kind} fins in box {fin_box}, so it can not be in those lines within box {fin_box}",
+                                join_houses(&cover_houses),
+                                join_houses(&base_houses)
+                            ))
+                            .with_houses(base_houses.clone(), cover_houses),
+                    );
+                }
+            }
+        }
+    }
+
+    deductions
+}
+
+/// The line through the position that crosses the given row or column.
+fn crossing_line(line: &House, position: &FieldPosition) -> House {
+    match line {
+        House::Row(_) => House::Column(position.column()),
+        _ => House::Row(position.row()),
+    }
+}
+
+fn fish_eliminations(
+    grid: &SudokuGrid,
+    digit: usize,
+    base_houses: &[House],
+    cover_houses: &[House],
+    fin_box: Option<usize>,
+) -> Vec<(FieldPosition, SolveStep)> {
+    cover_houses
+        .iter()
+        .flat_map(|cover| grid.get_fields_in_house(*cover))
+        .filter(|f| !f.is_filled() && f.possibilities().contains(digit))
+        .filter(|f| !base_houses.iter().any(|base| base.contains(f.position())))
+        .filter(|f| fin_box.is_none_or(|b| f.position().box_id() == b))
+        .map(|f| {
+            (
+                f.position().clone(),
+                SolveStep::RemovePossibilities(Candidates::single(digit)),
+            )
+        })
+        .collect()
+}
+
+fn fish_justification(
+    digit: usize,
+    positions: &[&FieldPosition],
+) -> Vec<(FieldPosition, Candidates)> {
+    positions
+        .iter()
+        .map(|p| ((*p).clone(), Candidates::single(digit)))
+        .collect()
+}
+
+fn join_houses(houses: &[House]) -> String {
+    houses
+        .iter()
+        .map(|h| h.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// A pivot with the candidates `xy` sees two pincers `xz` and `yz`. Whichever digit the pivot
+/// takes, one of the pincers is `z`, so `z` can be removed from every field seeing both pincers.
+pub(crate) fn remove_possibilities_by_xy_wing(grid: &SudokuGrid) -> Vec<Deduction> {
+    let mut deductions = vec![];
+    let bivalue_fields = fields_with_possibility_count(grid, 2);
+
+    for pivot in bivalue_fields.iter() {
+        let pincers = bivalue_fields
+            .iter()
+            .filter(|f| f.position().sees(pivot.position()))
+            .filter(|f| (f.possibilities() & pivot.possibilities()).len() == 1)
+            .collect::<Vec<_>>();
+
+        for (i, a) in pincers.iter().enumerate() {
+            for b in pincers[i + 1..].iter() {
+                let z = a.possibilities() - pivot.possibilities();
+                if z != b.possibilities() - pivot.possibilities()
+                    || (a.possibilities() & b.possibilities()) != z
+                {
+                    continue;
+                }
+
+                let Some(digit) = z.first() else {
+                    continue;
+                };
+
+                let wing = [*pivot, **a, **b];
+                let solve_steps =
+                    remove_digit_from_common_peers(grid, digit, &[a.position(), b.position()]);
+                if solve_steps.is_empty() {
+                    continue;
+                }
+
+                deductions.push(
+                    Deduction::new(solve_steps, fields_justification(&wing)).with_explanation(
+                        format!(
+                            "{} holds one of {}, either way {} or {} is {digit}, so {digit} can not be in a field seeing both",
+                            pivot.position(),
+                            join_digits(pivot.possibilities()),
+                            a.position(),
+                            b.position()
+                        ),
+                    ),
+                );
+            }
+        }
+    }
+
+    deductions
+}
+
+/// Like an XY-Wing, but the pivot has the candidates `xyz` itself, so `z` can only be removed
+/// from fields seeing the pivot and both pincers.
+pub(crate) fn remove_possibilities_by_xyz_wing(grid: &SudokuGrid) -> Vec<Deduction> {
+    let mut deductions = vec![];
+    let bivalue_fields = fields_with_possibility_count(grid, 2);
+
+    for pivot in fields_with_possibility_count(grid, 3) {
+        let pincers = bivalue_fields
+            .iter()
+            .filter(|f| f.position().sees(pivot.position()))
+            .filter(|f| f.possibilities().is_subset(&pivot.possibilities()))
+            .collect::<Vec<_>>();
+
+        for (i, a) in pincers.iter().enumerate() {
+            for b in pincers[i + 1..].iter() {
+                let z = a.possibilities() & b.possibilities();
+                if z.len() != 1 || (a.possibilities() | b.possibilities()) != pivot.possibilities()
+                {
+                    continue;
+                }
+
+                let Some(digit) = z.first() else {
+                    continue;
+                };
+
+                let wing = [pivot, *a, *b];
+                let positions = wing.map(|f| f.position());
+                let solve_steps = remove_digit_from_common_peers(grid, digit, &positions);
+                if solve_steps.is_empty() {
+                    continue;
+                }
+
+                deductions.push(
+                    Deduction::new(solve_steps, fields_justification(&wing)).with_explanation(
+                        format!(
+                            "one of {}, {} and {} is {digit}, so {digit} can not be in a field seeing all of them",
+                            pivot.position(),
+                            a.position(),
+                            b.position()
+                        ),
+                    ),
+                );
+            }
+        }
+    }
+
+    deductions
+}
+
+/// Two fields with the same candidates `xy` that do not see each other, connected by a strong
+/// link on `x`: a house where `x` fits into two fields only, each seeing one of them. One of the
+/// two fields is `y` then, so `y` can be removed from every field seeing both.
+pub(crate) fn remove_possibilities_by_w_wing(grid: &SudokuGrid) -> Vec<Deduction> {
+    let mut deductions = vec![];
+    let bivalue_fields = fields_with_possibility_count(grid, 2);
+
+    for (i, a) in bivalue_fields.iter().enumerate() {
+        for b in bivalue_fields[i + 1..].iter() {
+            if a.possibilities() != b.possibilities() || a.position().sees(b.position()) {
+                continue;
+            }
+
+            for x in a.possibilities() {
+                let Some(y) = (a.possibilities() - Candidates::single(x)).first() else {
+                    continue;
+                };
+
+                for house in House::all() {
+                    let Some([c, d]) = conjugate_pair(grid, house, x) else {
+                        continue;
+                    };
+
+                    let connected = (c.position().sees(a.position())
+                        && d.position().sees(b.position()))
+                        || (c.position().sees(b.position()) && d.position().sees(a.position()));
+                    if !connected {
+                        continue;
+                    }
+
+                    let solve_steps =
+                        remove_digit_from_common_peers(grid, y, &[a.position(), b.position()]);
+                    if solve_steps.is_empty() {
+                        continue;
+                    }
+
+                    let mut justification = fields_justification(&[a, b]);
+                    justification.push((c.position().clone(), Candidates::single(x)));
+                    justification.push((d.position().clone(), Candidates::single(x)));
+
+                    deductions.push(
+                        Deduction::new(solve_steps, justification)
+                            .with_explanation(format!(
+                                "{x} is in {} or {} of {house}, so {} or {} is {y} and {y} can not be in a field seeing both",
+                                c.position(),
+                                d.position(),
+                                a.position(),
+                                b.position()
+                            ))
+                            .with_houses(vec![house], vec![]),
+                    );
                 }
+            }
+        }
+    }
+
+    deductions
+}
 
-                possibilities
+/// The shapes of a chain of two conjugate pairs, connected by fields that see each other.
+#[derive(PartialEq)]
+enum TurbotShape {
+    /// Both pairs are in parallel lines and connected through a crossing line.
+    Skyscraper,
+    /// A row pair and a column pair connected through a box.
+    TwoStringKite,
+    /// Any other combination, e.g. with a pair in a box.
+    TurbotFish,
+}
+
+impl TurbotShape {
+    fn of(
+        first: &ConjugatePair,
+        second: &ConjugatePair,
+        b: &FieldPosition,
+        c: &FieldPosition,
+    ) -> Self {
+        match (first.house, second.house) {
+            (House::Row(_), House::Row(_)) if b.column() == c.column() => TurbotShape::Skyscraper,
+            (House::Column(_), House::Column(_)) if b.row() == c.row() => TurbotShape::Skyscraper,
+            (House::Row(_), House::Column(_)) | (House::Column(_), House::Row(_))
+                if b.box_id() == c.box_id() =>
+            {
+                TurbotShape::TwoStringKite
+            }
+            _ => TurbotShape::TurbotFish,
+        }
+    }
+}
+
+pub(crate) fn remove_possibilities_by_skyscraper(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_turbot_shape(grid, TurbotShape::Skyscraper)
+}
+
+pub(crate) fn remove_possibilities_by_two_string_kite(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_turbot_shape(grid, TurbotShape::TwoStringKite)
+}
+
+pub(crate) fn remove_possibilities_by_turbot_fish(grid: &SudokuGrid) -> Vec<Deduction> {
+    remove_possibilities_by_turbot_shape(grid, TurbotShape::TurbotFish)
+}
+
+/// The chain `a = b - c = d`: the digit is in `a` or `b` and in `c` or `d`. As `b` and `c` see
+/// each other, at least one of `a` and `d` holds the digit and it can be removed from every field
+/// seeing both.
+fn remove_possibilities_by_turbot_shape(grid: &SudokuGrid, shape: TurbotShape) -> Vec<Deduction> {
+    let mut deductions = vec![];
+
+    for digit in 1..=9 {
+        let graph = ConjugatePairs::new(grid, digit);
+        let pairs = graph.pairs();
+
+        for (i, first) in pairs.iter().enumerate() {
+            for second in pairs[i + 1..].iter() {
+                let [p, q] = &first.positions;
+                let [r, t] = &second.positions;
+
+                for [a, b] in [[p, q], [q, p]] {
+                    for [c, d] in [[r, t], [t, r]] {
+                        if first.contains(c) || first.contains(d) || !b.sees(c) {
+                            continue;
+                        }
+
+                        if TurbotShape::of(first, second, b, c) != shape {
+                            continue;
+                        }
+
+                        let solve_steps = remove_digit_from_common_peers(grid, digit, &[a, d]);
+                        if solve_steps.is_empty() {
+                            continue;
+                        }
+
+                        let justification = [a, b, c, d]
+                            .iter()
+                            .map(|p| ((*p).clone(), Candidates::single(digit)))
+                            .collect();
+                        let node = |p: &FieldPosition| ChainNode::new(p.clone(), digit);
+
+                        deductions.push(
+                            Deduction::new(solve_steps, justification)
+                                .with_explanation(format!(
+                                    "{digit} is in {a} or {b} of {} and in {c} or {d} of {}, {b} and {c} see each other, so {a} or {d} is {digit} and {digit} can not be in a field seeing both",
+                                    first.house, second.house
+                                ))
+                                .with_houses(vec![first.house, second.house], vec![])
+                                .with_links(vec![
+                                    Link::strong(node(a), node(b)),
+                                    Link::weak(node(b), node(c)),
+                                    Link::strong(node(c), node(d)),
+                                ]),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    deductions
+}
+
+/// Colors every connected group of conjugate pairs of a digit with two colors, one of which is
+/// true. If two fields of the same color see each other, that color is false (color wrap). A field
+/// outside the group that sees both colors can not hold the digit (color trap).
+pub(crate) fn remove_possibilities_by_simple_coloring(grid: &SudokuGrid) -> Vec<Deduction> {
+    let mut deductions = vec![];
+
+    for digit in 1..=9 {
+        let graph = ConjugatePairs::new(grid, digit);
+
+        for cluster in graph.clusters() {
+            let positions = cluster
+                .iter()
+                .map(|(p, _)| p.clone())
+                .collect::<Vec<FieldPosition>>();
+            let deduction = |solve_steps: Vec<(FieldPosition, SolveStep)>, explanation: String| {
+                Deduction::new(
+                    solve_steps,
+                    positions
+                        .iter()
+                        .map(|p| (p.clone(), Candidates::single(digit)))
+                        .collect(),
+                )
+                .with_explanation(explanation)
+                .with_links(graph.links_between(&positions))
+            };
+
+            for color in [true, false] {
+                let colored = cluster
                     .iter()
-                    .all(|p| field_possibilities.contains(p))
+                    .filter(|(_, c)| *c == color)
+                    .map(|(p, _)| p)
+                    .collect::<Vec<&FieldPosition>>();
+                let conflict = colored.iter().enumerate().find_map(|(i, p)| {
+                    colored[i + 1..]
+                        .iter()
+                        .find(|q| p.sees(q))
+                        .map(|q| (*p, *q))
+                });
+
+                if let Some((p, q)) = conflict {
+                    let solve_steps = colored
+                        .iter()
+                        .map(|p| {
+                            (
+                                (*p).clone(),
+                                SolveStep::RemovePossibilities(Candidates::single(digit)),
+                            )
+                        })
+                        .collect();
+
+                    deductions.push(deduction(
+                        solve_steps,
+                        format!(
+                            "{p} and {q} have the same color in the chain of {digit} and see each other, so {digit} can not be in any field of that color"
+                        ),
+                    ));
+                }
+            }
+
+            let solve_steps = grid
+                .fields()
+                .into_iter()
+                .filter(|f| !f.is_filled() && f.possibilities().contains(digit))
+                .filter(|f| !positions.contains(f.position()))
+                .filter(|f| {
+                    [true, false].iter().all(|color| {
+                        cluster
+                            .iter()
+                            .any(|(p, c)| c == color && p.sees(f.position()))
+                    })
+                })
+                .map(|f| {
+                    (
+                        f.position().clone(),
+                        SolveStep::RemovePossibilities(Candidates::single(digit)),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            if !solve_steps.is_empty() {
+                deductions.push(deduction(
+                    solve_steps,
+                    format!(
+                        "one of both colors in the chain of {digit} is true, so {digit} can not be in a field seeing both colors"
+                    ),
+                ));
+            }
+        }
+    }
+
+    deductions
+}
+
+/// Four unfilled fields in two rows, two columns and two boxes which all can hold the digits `x`
+/// and `y`. If all of them could hold nothing else, both digits could be swapped and the puzzle
+/// would not have a single solution. These strategies assume that it has one.
+struct UniqueRectangle<'a> {
+    /// Ordered top left, top right, bottom left, bottom right.
+    corners: [&'a Field; 4],
+    x: usize,
+    y: usize,
+}
+
+impl<'a> UniqueRectangle<'a> {
+    /// The pairs of corners that form opposite sides of the rectangle.
+    const SIDES: [([usize; 2], [usize; 2]); 4] = [
+        ([0, 1], [2, 3]),
+        ([2, 3], [0, 1]),
+        ([0, 2], [1, 3]),
+        ([1, 3], [0, 2]),
+    ];
+
+    fn find_all(grid: &'a SudokuGrid) -> Vec<Self> {
+        let mut rectangles = vec![];
+
+        for (r1, r2) in (0..9).flat_map(|r1| (r1 + 1..9).map(move |r2| (r1, r2))) {
+            for (c1, c2) in (0..9).flat_map(|c1| (c1 + 1..9).map(move |c2| (c1, c2))) {
+                if (r1 / 3 == r2 / 3) == (c1 / 3 == c2 / 3) {
+                    continue;
+                }
+
+                let corners = [(r1, c1), (r1, c2), (r2, c1), (r2, c2)]
+                    .map(|(r, c)| grid.get_field(FieldPosition::new(r, c)));
+                let [Some(a), Some(b), Some(c), Some(d)] = corners else {
+                    continue;
+                };
+                let corners = [a, b, c, d];
+                if corners.iter().any(|f| f.is_filled()) {
+                    continue;
+                }
+
+                let common = corners
+                    .iter()
+                    .fold(Candidates::all(), |common, f| common & f.possibilities());
+                for pair in combinations(&common.iter().collect::<Vec<usize>>(), 2) {
+                    rectangles.push(Self {
+                        corners,
+                        x: pair[0],
+                        y: pair[1],
+                    });
+                }
+            }
+        }
+
+        rectangles
+    }
+
+    fn digits(&self) -> Candidates {
+        Candidates::from_iter([self.x, self.y])
+    }
+
+    /// The candidates of a field besides `x` and `y`.
+    fn extras(&self, field: &Field) -> Candidates {
+        field.possibilities() - self.digits()
+    }
+
+    /// Every side whose fields can only hold `x` and `y` (the floor), together with the
+    /// opposite side whose fields can both hold more (the roof).
+    fn floors_and_roofs(&self) -> Vec<([&'a Field; 2], [&'a Field; 2])> {
+        Self::SIDES
+            .iter()
+            .map(|(floor, roof)| {
+                (
+                    floor.map(|i| self.corners[i]),
+                    roof.map(|i| self.corners[i]),
+                )
             })
-            .collect::<Vec<&Vec<usize>>>();
+            .filter(|(floor, roof)| {
+                floor.iter().all(|f| self.extras(f).is_empty())
+                    && roof.iter().all(|f| !self.extras(f).is_empty())
+            })
+            .collect()
+    }
+
+    fn deduction(
+        &self,
+        solve_steps: Vec<(FieldPosition, SolveStep)>,
+        conclusion: String,
+    ) -> Deduction {
+        let [a, b, c, d] = self.corners.map(|f| f.position());
+        Deduction::new(solve_steps, fields_justification(&self.corners)).with_explanation(format!(
+            "{a}, {b}, {c} and {d} would allow to swap {} and {} if none of them could hold anything else, {conclusion}",
+            self.x, self.y
+        ))
+    }
+}
 
-        if matches.len() == len {
-            possibilties_to_remove.append(&mut field_possibilities.clone());
+/// The houses both fields are in.
+fn common_houses(a: &FieldPosition, b: &FieldPosition) -> Vec<House> {
+    House::all()
+        .filter(|house| house.contains(a) && house.contains(b))
+        .collect()
+}
+
+/// Three corners can only hold `x` and `y`, so the fourth one can not hold either of them.
+pub(crate) fn remove_possibilities_by_unique_rectangle_type_1(grid: &SudokuGrid) -> Vec<Deduction> {
+    let mut deductions = vec![];
+
+    for rectangle in UniqueRectangle::find_all(grid) {
+        let with_extras = rectangle
+            .corners
+            .iter()
+            .filter(|f| !rectangle.extras(f).is_empty())
+            .collect::<Vec<_>>();
+        let [field] = with_extras[..] else {
+            continue;
+        };
+
+        deductions.push(rectangle.deduction(
+            vec![(
+                field.position().clone(),
+                SolveStep::RemovePossibilities(rectangle.digits()),
+            )],
+            format!(
+                "so {} can be neither {} nor {}",
+                field.position(),
+                rectangle.x,
+                rectangle.y
+            ),
+        ));
+    }
+
+    deductions
+}
+
+/// Both roof fields have the same single extra candidate `z`. One of them is `z`, so `z` can be
+/// removed from every field seeing both.
+pub(crate) fn remove_possibilities_by_unique_rectangle_type_2(grid: &SudokuGrid) -> Vec<Deduction> {
+    let mut deductions = vec![];
+
+    for rectangle in UniqueRectangle::find_all(grid) {
+        for (_, [a, b]) in rectangle.floors_and_roofs() {
+            let extras = rectangle.extras(a);
+            if extras != rectangle.extras(b) || extras.len() != 1 {
+                continue;
+            }
+            let Some(z) = extras.first() else {
+                continue;
+            };
+
+            let solve_steps =
+                remove_digit_from_common_peers(grid, z, &[a.position(), b.position()]);
+            if solve_steps.is_empty() {
+                continue;
+            }
+
+            deductions.push(rectangle.deduction(
+                solve_steps,
+                format!(
+                    "so {} or {} is {z} and {z} can not be in a field seeing both",
+                    a.position(),
+                    b.position()
+                ),
+            ));
         }
     }
 
-    possibilties_to_remove.sort();
-    possibilties_to_remove.dedup();
+    deductions
+}
 
-    possibilties_to_remove
+/// The extra candidates of the roof fields act like a single field of a house both are in. If they
+/// form a naked subset together with other fields of that house, the digits of the subset can be
+/// removed from the rest of the house.
+pub(crate) fn remove_possibilities_by_unique_rectangle_type_3(grid: &SudokuGrid) -> Vec<Deduction> {
+    let mut deductions = vec![];
+
+    for rectangle in UniqueRectangle::find_all(grid) {
+        for (_, roof) in rectangle.floors_and_roofs() {
+            let extras = rectangle.extras(roof[0]) | rectangle.extras(roof[1]);
+
+            for house in common_houses(roof[0].position(), roof[1].position()) {
+                let others = grid
+                    .get_fields_in_house(house)
+                    .into_iter()
+                    .filter(|f| !f.is_filled() && roof.iter().all(|r| r.position() != f.position()))
+                    .collect::<Vec<&Field>>();
+
+                for size in 1..=3 {
+                    for subset in combinations(&others, size) {
+                        let digits = subset
+                            .iter()
+                            .fold(extras, |digits, f| digits | f.possibilities());
+                        if digits.len() != size + 1 {
+                            continue;
+                        }
+
+                        let solve_steps = others
+                            .iter()
+                            .filter(|f| subset.iter().all(|s| s.position() != f.position()))
+                            .filter(|f| !(f.possibilities() & digits).is_empty())
+                            .map(|f| {
+                                (
+                                    f.position().clone(),
+                                    SolveStep::RemovePossibilities(f.possibilities() & digits),
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        if solve_steps.is_empty() {
+                            continue;
+                        }
+
+                        let mut justification = fields_justification(&rectangle.corners);
+                        justification.extend(fields_justification(&subset));
+
+                        deductions.push(
+                            Deduction::new(solve_steps, justification)
+                                .with_explanation(format!(
+                                    "{} or {} holds one of {} to avoid a second solution on {} and {}, together with {} they lock {} in {house}",
+                                    roof[0].position(),
+                                    roof[1].position(),
+                                    join_digits(extras),
+                                    rectangle.x,
+                                    rectangle.y,
+                                    subset
+                                        .iter()
+                                        .map(|f| f.position().to_string())
+                                        .collect::<Vec<String>>()
+                                        .join(", "),
+                                    join_digits(digits)
+                                ))
+                                .with_houses(vec![house], vec![]),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    deductions
 }
 
-pub(crate) fn remove_possibilities_by_pairs_of_size_nin_colummn(
-    field: &Field,
-    grid: &SudokuGrid,
-) -> Option<SolveStep> {
-    let fields = grid.get_fields_in_column(field.position().column());
+/// One digit of the rectangle can only be in the roof fields within a house both are in. One of
+/// them holds it, so neither may hold the other digit of the rectangle.
+pub(crate) fn remove_possibilities_by_unique_rectangle_type_4(grid: &SudokuGrid) -> Vec<Deduction> {
+    let mut deductions = vec![];
+
+    for rectangle in UniqueRectangle::find_all(grid) {
+        for (_, roof) in rectangle.floors_and_roofs() {
+            for house in common_houses(roof[0].position(), roof[1].position()) {
+                for (locked, removed) in [(rectangle.x, rectangle.y), (rectangle.y, rectangle.x)] {
+                    let Some(pair) = conjugate_pair(grid, house, locked) else {
+                        continue;
+                    };
+                    if !pair
+                        .iter()
+                        .all(|f| roof.iter().any(|r| r.position() == f.position()))
+                    {
+                        continue;
+                    }
+
+                    let solve_steps = roof
+                        .iter()
+                        .map(|f| {
+                            (
+                                f.position().clone(),
+                                SolveStep::RemovePossibilities(Candidates::single(removed)),
+                            )
+                        })
+                        .collect();
 
-    let fields_possibilities = fields
+                    deductions.push(
+                        rectangle
+                            .deduction(
+                                solve_steps,
+                                format!(
+                                    "{locked} is in {} or {} of {house}, so neither can be {removed}",
+                                    roof[0].position(),
+                                    roof[1].position()
+                                ),
+                            )
+                            .with_houses(vec![house], vec![]),
+                    );
+                }
+            }
+        }
+    }
+
+    deductions
+}
+
+/// Every unfilled field but one can hold exactly two digits and every digit fits into either no
+/// or two fields of each house. Such a grid would have more than one solution, so the one field
+/// with three candidates must hold the digit that fits three times into its houses.
+pub(crate) fn set_value_by_bivalue_universal_grave(grid: &SudokuGrid) -> Vec<Deduction> {
+    let mut unfilled = grid
+        .fields()
+        .into_iter()
+        .filter(|f| !f.is_filled() && f.possibilities().len() != 2);
+    let (Some(field), None) = (unfilled.next(), unfilled.next()) else {
+        return vec![];
+    };
+    if field.possibilities().len() != 3 {
+        return vec![];
+    }
+
+    let count = |house: House, digit: usize| {
+        grid.get_fields_in_house(house)
+            .iter()
+            .filter(|f| !f.is_filled() && f.possibilities().contains(digit))
+            .count()
+    };
+    let position = field.position();
+    let houses = [
+        House::Row(position.row()),
+        House::Column(position.column()),
+        House::Box(position.box_id()),
+    ];
+    let Some(digit) = field
+        .possibilities()
         .iter()
-        .filter(|f| f.position().row() != field.position().row())
-        .map(|field| field.possibilities().clone())
-        .collect::<Vec<Vec<usize>>>();
+        .find(|digit| houses.iter().all(|house| count(*house, *digit) == 3))
+    else {
+        return vec![];
+    };
 
-    let possibilities_to_remove = find_grouped_possibilities(fields_possibilities);
-    if possibilities_to_remove.is_empty() {
-        None
-    } else {
-        Some(SolveStep::RemovePossibilities(possibilities_to_remove))
+    let is_grave = House::all().all(|house| {
+        (1..=9).all(|d| match count(house, d) {
+            0 | 2 => true,
+            3 => d == digit && house.contains(position),
+            _ => false,
+        })
+    });
+    if !is_grave {
+        return vec![];
     }
+
+    vec![
+        Deduction::set_value(position.clone(), digit, fields_justification(&[field]))
+            .with_explanation(format!(
+                "every other field can hold two digits only and every other digit fits into two fields per house, so {position} must be {digit} to avoid a second solution"
+            ))
+            .with_houses(houses.to_vec(), vec![]),
+    ]
+}
+
+fn fields_with_possibility_count(grid: &SudokuGrid, count: usize) -> Vec<&Field> {
+    grid.fields()
+        .into_iter()
+        .filter(|f| !f.is_filled() && f.possibilities().len() == count)
+        .collect()
 }
-pub(crate) fn remove_possibilities_by_pairs_of_size_nin_box(
-    field: &Field,
+
+/// Removes the digit from every field that sees all of the given positions.
+fn remove_digit_from_common_peers(
     grid: &SudokuGrid,
-) -> Option<SolveStep> {
-    let box_id = SudokuGrid::get_box_id_for_field(field)?;
-    let fields = grid.get_fields_in_box(box_id);
+    digit: usize,
+    positions: &[&FieldPosition],
+) -> Vec<(FieldPosition, SolveStep)> {
+    let Some(first) = positions.first() else {
+        return vec![];
+    };
 
-    let fields_possibilities = fields
-        .iter()
-        .filter(|f| {
-            f.position().column() != field.position().column()
-                && f.position().row() != field.position().row()
+    grid.get_peers(first)
+        .into_iter()
+        .filter(|f| !f.is_filled() && f.possibilities().contains(digit))
+        .filter(|f| positions.iter().all(|other| f.position().sees(other)))
+        .map(|f| {
+            (
+                f.position().clone(),
+                SolveStep::RemovePossibilities(Candidates::single(digit)),
+            )
         })
-        .map(|field| field.possibilities().clone())
-        .collect::<Vec<Vec<usize>>>();
+        .collect()
+}
+
+fn fields_justification(fields: &[&Field]) -> Vec<(FieldPosition, Candidates)> {
+    fields
+        .iter()
+        .map(|f| (f.position().clone(), f.possibilities()))
+        .collect()
+}
 
-    let possibilities_to_remove = find_grouped_possibilities(fields_possibilities);
-    if possibilities_to_remove.is_empty() {
-        None
-    } else {
-        Some(SolveStep::RemovePossibilities(possibilities_to_remove))
+/// Every way to pick `size` items, keeping their order.
+fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![vec![]];
     }
+
+    items
+        .iter()
+        .enumerate()
+        .flat_map(|(i, item)| {
+            combinations(&items[i + 1..], size - 1)
+                .into_iter()
+                .map(move |mut combination| {
+                    combination.insert(0, *item);
+                    combination
+                })
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::solver::{Deduction, SolveStep, SudokuSolver};
+    use crate::sudoku::candidates::Candidates;
+    use crate::sudoku::field::FieldPosition;
+    use crate::sudoku::grid::SudokuGrid;
+    use crate::sudoku::house::House;
+
+    fn removed_positions(deductions: Vec<Deduction>, digit: usize) -> Vec<FieldPosition> {
+        deductions
+            .into_iter()
+            .flat_map(|d| d.solve_steps)
+            .filter(
+                |(_, step)| matches!(step, SolveStep::RemovePossibilities(p) if p.contains(digit)),
+            )
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// The groups of every size together.
+    fn find_grouped_possibilities(fields_possibilities: Vec<Vec<usize>>) -> Vec<usize> {
+        let fields_possibilities = fields_possibilities
+            .into_iter()
+            .map(Candidates::from_iter)
+            .collect::<Vec<Candidates>>();
+
+        (2..=9)
+            .map(|size| super::find_grouped_possibilities(fields_possibilities.clone(), size))
+            .fold(Candidates::empty(), |all, grouped| all | grouped)
+            .iter()
+            .collect()
+    }
+
+    fn find_hidden_subsets(
+        fields_possibilities: Vec<Vec<usize>>,
+        size: usize,
+    ) -> Vec<(Vec<usize>, Vec<usize>)> {
+        super::find_hidden_subsets(
+            fields_possibilities
+                .into_iter()
+                .map(Candidates::from_iter)
+                .collect(),
+            size,
+        )
+        .into_iter()
+        .map(|(digits, indexes)| (digits.iter().collect(), indexes))
+        .collect()
+    }
+
     #[test]
     fn it_works_for_pairs() {
         let possibilities = vec![vec![1, 2], vec![1, 2], vec![2, 3], vec![1, 2, 4, 5]];
         let result = vec![1, 2];
 
-        assert_eq!(result, super::find_grouped_possibilities(possibilities));
+        assert_eq!(result, find_grouped_possibilities(possibilities));
     }
 
     #[test]
@@ -259,7 +1700,7 @@ mod tests {
         ];
         let result = vec![1, 2, 3];
 
-        assert_eq!(result, super::find_grouped_possibilities(possibilities));
+        assert_eq!(result, find_grouped_possibilities(possibilities));
     }
 
     #[test]
@@ -267,12 +1708,12 @@ mod tests {
         let possibilities = vec![vec![1, 2, 3], vec![1, 3], vec![1, 2, 3], vec![1, 2, 4, 5]];
         let result = vec![1, 2, 3];
 
-        assert_eq!(result, super::find_grouped_possibilities(possibilities));
+        assert_eq!(result, find_grouped_possibilities(possibilities));
 
         let possibilities = vec![vec![1, 2, 3], vec![1, 3], vec![1, 2], vec![1, 2, 4, 5]];
         let result = vec![1, 2, 3];
 
-        assert_eq!(result, super::find_grouped_possibilities(possibilities));
+        assert_eq!(result, find_grouped_possibilities(possibilities));
 
         let possibilities = vec![
             vec![1, 2, 3],
@@ -282,7 +1723,7 @@ mod tests {
         ];
         let result = vec![1, 2, 3];
 
-        assert_eq!(result, super::find_grouped_possibilities(possibilities));
+        assert_eq!(result, find_grouped_possibilities(possibilities));
         let possibilities = vec![
             vec![1, 2, 3],
             vec![1, 3],
@@ -292,14 +1733,481 @@ mod tests {
         ];
         let result = vec![1, 2, 3, 4, 5];
 
-        assert_eq!(result, super::find_grouped_possibilities(possibilities));
+        assert_eq!(result, find_grouped_possibilities(possibilities));
         let possibilities = vec![vec![1, 5, 8], vec![1, 4, 5, 8], vec![], vec![]];
         let result: Vec<usize> = vec![];
 
-        assert_eq!(result, super::find_grouped_possibilities(possibilities));
+        assert_eq!(result, find_grouped_possibilities(possibilities));
         let possibilities = vec![vec![1, 5, 8], vec![1, 4, 5, 8], vec![2], vec![3]];
         let result: Vec<usize> = vec![];
 
-        assert_eq!(result, super::find_grouped_possibilities(possibilities));
+        assert_eq!(result, find_grouped_possibilities(possibilities));
+    }
+
+    #[test]
+    fn it_only_groups_subsets_of_the_given_size() {
+        let possibilities = [
+            vec![1, 2],
+            vec![1, 2],
+            vec![3, 4, 5],
+            vec![3, 4, 5],
+            vec![3, 5],
+        ]
+        .map(Candidates::from_iter)
+        .to_vec();
+
+        assert_eq!(
+            Candidates::from_iter([1, 2]),
+            super::find_grouped_possibilities(possibilities.clone(), 2)
+        );
+        assert_eq!(
+            Candidates::from_iter([3, 4, 5]),
+            super::find_grouped_possibilities(possibilities.clone(), 3)
+        );
+        assert_eq!(
+            Candidates::empty(),
+            super::find_grouped_possibilities(possibilities, 4)
+        );
+    }
+
+    #[test]
+    fn it_removes_pointing_candidates_from_the_rest_of_the_line() {
+        let mut values = [[0; 9]; 9];
+        values[1][0..3].copy_from_slice(&[2, 3, 4]);
+        values[2][0..3].copy_from_slice(&[5, 6, 7]);
+        let grid = SudokuGrid::from(values);
+
+        let removed =
+            removed_positions(super::remove_possibilities_by_pointing_candidates(&grid), 1);
+
+        assert_eq!(
+            (3..9).map(|c| FieldPosition::new(0, c)).collect::<Vec<_>>(),
+            removed
+        );
+    }
+
+    #[test]
+    fn it_removes_claiming_candidates_from_the_rest_of_the_box() {
+        let mut values = [[0; 9]; 9];
+        values[0][3..9].copy_from_slice(&[2, 3, 4, 5, 6, 7]);
+        let grid = SudokuGrid::from(values);
+
+        let removed =
+            removed_positions(super::remove_possibilities_by_claiming_candidates(&grid), 1);
+
+        assert_eq!(
+            [(1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+                .map(|(r, c)| FieldPosition::new(r, c))
+                .to_vec(),
+            removed
+        );
+    }
+
+    #[test]
+    fn it_finds_hidden_pairs() {
+        let possibilities = vec![
+            vec![1, 2, 5],
+            vec![3, 5, 6],
+            vec![1, 2, 6],
+            vec![3, 4, 6],
+            vec![4, 5, 6],
+        ];
+        let result = vec![(vec![1, 2], vec![0, 2])];
+
+        assert_eq!(result, find_hidden_subsets(possibilities, 2));
+
+        let possibilities = vec![vec![1, 2, 5], vec![1, 2, 5], vec![1, 2, 5]];
+        let result: Vec<(Vec<usize>, Vec<usize>)> = vec![];
+
+        assert_eq!(result, find_hidden_subsets(possibilities, 2));
+    }
+
+    #[test]
+    fn it_finds_hidden_triples_with_missing_possibilities() {
+        let possibilities = vec![
+            vec![1, 2, 4, 5],
+            vec![1, 3, 4, 6],
+            vec![4, 5, 6, 7],
+            vec![2, 3, 5, 7],
+            vec![4, 5, 6, 7],
+            vec![4, 6, 7],
+        ];
+        let result = vec![(vec![1, 2, 3], vec![0, 1, 3])];
+
+        assert_eq!(result, find_hidden_subsets(possibilities, 3));
+    }
+
+    #[test]
+    fn it_finds_hidden_quads() {
+        let possibilities = vec![
+            vec![1, 2, 5, 6],
+            vec![3, 4, 7, 8],
+            vec![1, 3, 5, 9],
+            vec![2, 4, 6, 7],
+            vec![5, 6, 7, 8, 9],
+            vec![5, 6, 7, 8, 9],
+            vec![5, 6, 7, 8, 9],
+            vec![5, 6, 7, 8, 9],
+            vec![5, 6, 7, 8, 9],
+        ];
+        let result = vec![(vec![1, 2, 3, 4], vec![0, 1, 2, 3])];
+
+        assert_eq!(result, find_hidden_subsets(possibilities, 4));
+    }
+
+    #[test]
+    fn it_names_the_house_of_a_hidden_pair() {
+        // 1 and 2 can only go into 0 / 0 and 0 / 1 of row 0
+        let without_1_and_2: &[usize] = &[3, 4, 5, 6, 7, 8, 9];
+        let fields = (2..9)
+            .map(|c| ((0, c), without_1_and_2))
+            .collect::<Vec<_>>();
+        let grid = SudokuGrid::with_possibilities(&fields);
+
+        let deductions = super::remove_possibilities_by_hidden_pairs(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(vec![House::Row(0)], deductions[0].base_houses);
+        assert_eq!(
+            "1 and 2 can only go into 2 fields of row 0, so those fields can not hold anything else",
+            deductions[0].explanation
+        );
+        assert_eq!(
+            vec![FieldPosition::new(0, 0), FieldPosition::new(0, 1)],
+            removed_positions(deductions, 3)
+        );
+    }
+
+    fn grid_with_removed_possibilities(digit: usize, positions: &[(usize, usize)]) -> SudokuGrid {
+        let mut grid = SudokuGrid::from([[0; 9]; 9]);
+        grid.apply_solve_steps(
+            &positions
+                .iter()
+                .map(|(r, c)| {
+                    (
+                        FieldPosition::new(*r, *c),
+                        SolveStep::RemovePossibilities(Candidates::single(digit)),
+                    )
+                })
+                .collect(),
+        );
+        grid
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_x_wing() {
+        let removed = [1, 5]
+            .into_iter()
+            .flat_map(|r| [0, 1, 3, 4, 5, 6, 8].map(|c| (r, c)))
+            .collect::<Vec<_>>();
+        let grid = grid_with_removed_possibilities(1, &removed);
+
+        let deductions = super::remove_possibilities_by_x_wing(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(
+            vec![House::Row(1), House::Row(5)],
+            deductions[0].base_houses
+        );
+        assert_eq!(
+            vec![House::Column(2), House::Column(7)],
+            deductions[0].cover_houses
+        );
+        assert_eq!(14, removed_positions(deductions, 1).len());
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_finned_x_wing() {
+        let removed = [(1, 0), (1, 1), (1, 3), (1, 4), (1, 5), (1, 6), (1, 8)]
+            .into_iter()
+            .chain([(5, 0), (5, 1), (5, 3), (5, 4), (5, 5), (5, 6)])
+            .collect::<Vec<_>>();
+        let grid = grid_with_removed_possibilities(1, &removed);
+
+        assert!(super::remove_possibilities_by_x_wing(&grid).is_empty());
+        assert_eq!(
+            vec![FieldPosition::new(3, 7), FieldPosition::new(4, 7)],
+            removed_positions(super::remove_possibilities_by_finned_x_wing(&grid), 1)
+        );
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_xy_wing() {
+        let grid = SudokuGrid::with_possibilities(&[
+            ((0, 0), &[1, 2]),
+            ((0, 5), &[1, 3]),
+            ((4, 0), &[2, 3]),
+        ]);
+
+        assert_eq!(
+            vec![FieldPosition::new(4, 5)],
+            removed_positions(super::remove_possibilities_by_xy_wing(&grid), 3)
+        );
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_xyz_wing() {
+        let grid = SudokuGrid::with_possibilities(&[
+            ((0, 0), &[1, 2, 3]),
+            ((0, 5), &[1, 3]),
+            ((2, 1), &[2, 3]),
+        ]);
+
+        assert_eq!(
+            vec![FieldPosition::new(0, 1), FieldPosition::new(0, 2)],
+            removed_positions(super::remove_possibilities_by_xyz_wing(&grid), 3)
+        );
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_w_wing() {
+        let mut grid = SudokuGrid::with_possibilities(&[((0, 0), &[1, 2]), ((4, 8), &[1, 2])]);
+        grid.apply_solve_steps(
+            &(1..8)
+                .map(|c| {
+                    (
+                        FieldPosition::new(8, c),
+                        SolveStep::RemovePossibilities(Candidates::single(1)),
+                    )
+                })
+                .collect(),
+        );
+
+        assert_eq!(
+            vec![FieldPosition::new(0, 8), FieldPosition::new(4, 0)],
+            removed_positions(super::remove_possibilities_by_w_wing(&grid), 2)
+        );
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_skyscraper() {
+        let removed = (0..9)
+            .filter(|c| ![0, 4].contains(c))
+            .map(|c| (1, c))
+            .chain((0..9).filter(|c| ![0, 5].contains(c)).map(|c| (5, c)))
+            .collect::<Vec<_>>();
+        let grid = grid_with_removed_possibilities(1, &removed);
+
+        let deductions = super::remove_possibilities_by_skyscraper(&grid);
+
+        assert!(!deductions.is_empty());
+        assert_eq!(3, deductions[0].links.len());
+        assert_eq!(
+            vec![
+                FieldPosition::new(0, 5),
+                FieldPosition::new(2, 5),
+                FieldPosition::new(3, 4),
+                FieldPosition::new(4, 4)
+            ],
+            removed_positions(deductions, 1)
+        );
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_two_string_kite() {
+        // 1 is in 0 / 1 or 0 / 6 of row 0 and in 2 / 0 or 6 / 0 of column 0
+        let removed = (0..9)
+            .filter(|c| ![1, 6].contains(c))
+            .map(|c| (0, c))
+            .chain((1..9).filter(|r| ![2, 6].contains(r)).map(|r| (r, 0)))
+            .collect::<Vec<_>>();
+        let grid = grid_with_removed_possibilities(1, &removed);
+
+        let deductions = super::remove_possibilities_by_two_string_kite(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(
+            vec![House::Row(0), House::Column(0)],
+            deductions[0].base_houses
+        );
+        assert_eq!(
+            vec![FieldPosition::new(6, 6)],
+            removed_positions(deductions, 1)
+        );
+        assert!(super::remove_possibilities_by_skyscraper(&grid).is_empty());
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_turbot_fish() {
+        // 1 is in 0 / 0 or 2 / 2 of box 0 and in 4 / 2 or 4 / 7 of row 4
+        let removed = (0..3)
+            .flat_map(|r| (0..3).map(move |c| (r, c)))
+            .filter(|p| ![(0, 0), (2, 2)].contains(p))
+            .chain((0..9).filter(|c| ![2, 7].contains(c)).map(|c| (4, c)))
+            .collect::<Vec<_>>();
+        let grid = grid_with_removed_possibilities(1, &removed);
+
+        let deductions = super::remove_possibilities_by_turbot_fish(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(3, deductions[0].links.len());
+        assert_eq!(
+            vec![FieldPosition::new(0, 7)],
+            removed_positions(deductions, 1)
+        );
+        assert!(super::remove_possibilities_by_two_string_kite(&grid).is_empty());
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_simple_coloring() {
+        let removed = (1..9)
+            .filter(|c| *c != 4)
+            .map(|c| (0, c))
+            .chain((1..9).filter(|r| *r != 4).map(|r| (r, 4)))
+            .chain([0, 2, 3, 5, 6, 7, 8].map(|c| (4, c)))
+            .chain([0, 2, 3, 5, 6, 7, 8].map(|r| (r, 1)))
+            .collect::<Vec<_>>();
+        let grid = grid_with_removed_possibilities(1, &removed);
+
+        let removed = removed_positions(super::remove_possibilities_by_simple_coloring(&grid), 1);
+
+        for position in [(0, 0), (1, 1), (4, 4)] {
+            assert!(removed.contains(&FieldPosition::new(position.0, position.1)));
+        }
+        assert!(!removed.contains(&FieldPosition::new(0, 4)));
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_unique_rectangle_type_1() {
+        let grid = SudokuGrid::with_possibilities(&[
+            ((0, 0), &[1, 2]),
+            ((0, 3), &[1, 2]),
+            ((1, 0), &[1, 2]),
+            ((1, 3), &[1, 2, 5]),
+        ]);
+
+        let deductions = super::remove_possibilities_by_unique_rectangle_type_1(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(
+            vec![(
+                FieldPosition::new(1, 3),
+                SolveStep::RemovePossibilities(Candidates::from_iter([1, 2]))
+            )],
+            deductions[0].solve_steps
+        );
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_unique_rectangle_type_2() {
+        let grid = SudokuGrid::with_possibilities(&[
+            ((0, 0), &[1, 2]),
+            ((0, 3), &[1, 2]),
+            ((1, 0), &[1, 2, 5]),
+            ((1, 3), &[1, 2, 5]),
+        ]);
+
+        let deductions = super::remove_possibilities_by_unique_rectangle_type_2(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(
+            [1, 2, 4, 5, 6, 7, 8]
+                .map(|c| (
+                    FieldPosition::new(1, c),
+                    SolveStep::RemovePossibilities(Candidates::single(5))
+                ))
+                .to_vec(),
+            deductions[0].solve_steps
+        );
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_unique_rectangle_type_3() {
+        let grid = SudokuGrid::with_possibilities(&[
+            ((0, 0), &[1, 2]),
+            ((0, 3), &[1, 2]),
+            ((1, 0), &[1, 2, 5]),
+            ((1, 3), &[1, 2, 6]),
+            ((1, 6), &[5, 6]),
+        ]);
+
+        let deductions = super::remove_possibilities_by_unique_rectangle_type_3(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(vec![House::Row(1)], deductions[0].base_houses);
+        assert_eq!(
+            [1, 2, 4, 5, 7, 8]
+                .map(|c| (
+                    FieldPosition::new(1, c),
+                    SolveStep::RemovePossibilities(Candidates::from_iter([5, 6]))
+                ))
+                .to_vec(),
+            deductions[0].solve_steps
+        );
+    }
+
+    #[test]
+    fn it_removes_possibilities_by_unique_rectangle_type_4() {
+        // 1 can only be in the roof fields of row 1
+        let without_1: &[usize] = &[2, 3, 4, 5, 6, 7, 8, 9];
+        let mut fields = vec![
+            ((0, 0), &[1, 2][..]),
+            ((0, 3), &[1, 2]),
+            ((1, 0), &[1, 2, 5]),
+            ((1, 3), &[1, 2, 6]),
+        ];
+        fields.extend([1, 2, 4, 5, 6, 7, 8].map(|c| ((1, c), without_1)));
+        let grid = SudokuGrid::with_possibilities(&fields);
+
+        let deductions = super::remove_possibilities_by_unique_rectangle_type_4(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(
+            vec![
+                (
+                    FieldPosition::new(1, 0),
+                    SolveStep::RemovePossibilities(Candidates::single(2))
+                ),
+                (
+                    FieldPosition::new(1, 3),
+                    SolveStep::RemovePossibilities(Candidates::single(2))
+                ),
+            ],
+            deductions[0].solve_steps
+        );
+    }
+
+    #[test]
+    fn it_sets_value_by_bivalue_universal_grave() {
+        // Every field can be its digit of a solution or the next digit, which allows two
+        // solutions. The extra 5 in the top left field breaks that.
+        let fields = (0..9)
+            .flat_map(|r| (0..9).map(move |c| (r, c)))
+            .map(|(r, c)| {
+                let digit = (r * 3 + r / 3 + c) % 9 + 1;
+                let mut possibilities = vec![digit, digit % 9 + 1];
+                if (r, c) == (0, 0) {
+                    possibilities.push(5);
+                }
+                ((r, c), possibilities)
+            })
+            .collect::<Vec<_>>();
+        let fields = fields
+            .iter()
+            .map(|(position, possibilities)| (*position, possibilities.as_slice()))
+            .collect::<Vec<_>>();
+        let grid = SudokuGrid::with_possibilities(&fields);
+
+        let deductions = super::set_value_by_bivalue_universal_grave(&grid);
+
+        assert_eq!(1, deductions.len());
+        assert_eq!(
+            vec![(FieldPosition::new(0, 0), SolveStep::SetValue(5))],
+            deductions[0].solve_steps
+        );
+    }
+
+    #[test]
+    fn it_skips_uniqueness_strategies_if_disabled() {
+        let uses_unique_rectangles = |solver: SudokuSolver| {
+            solver
+                .enabled_strategies()
+                .any(|s| s.id() == "unique-rectangle-1")
+        };
+
+        assert!(uses_unique_rectangles(SudokuSolver::new()));
+        assert!(!uses_unique_rectangles(
+            SudokuSolver::new().with_uniqueness_strategies(false)
+        ));
     }
 }
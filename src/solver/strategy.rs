@@ -0,0 +1,76 @@
+use crate::sudoku::grid::SudokuGrid;
+
+use super::Deduction;
+
+pub type SolveFn = dyn Fn(&SudokuGrid) -> Vec<Deduction>;
+
+pub trait Strategy {
+    /// Stable identifier which can be used to enable or disable the strategy.
+    fn id(&self) -> &'static str;
+
+    /// Human readable name, e.g. for the history panel.
+    fn name(&self) -> &'static str;
+
+    /// Rating on the Sudoku Explainer scale, `0.0` for plain candidate eliminations.
+    fn difficulty(&self) -> f32;
+
+    /// Looks at the whole grid and returns every deduction the strategy can make.
+    fn find_deductions(&self, grid: &SudokuGrid) -> Vec<Deduction>;
+
+    /// Whether the deductions are only valid for puzzles with a single solution.
+    fn requires_uniqueness(&self) -> bool {
+        false
+    }
+}
+
+/// A strategy backed by a plain solve function.
+pub struct FnStrategy {
+    id: &'static str,
+    name: &'static str,
+    difficulty: f32,
+    requires_uniqueness: bool,
+    solve_fn: Box<SolveFn>,
+}
+
+impl FnStrategy {
+    pub fn new<F>(id: &'static str, name: &'static str, difficulty: f32, solve_fn: F) -> Self
+    where
+        F: Fn(&SudokuGrid) -> Vec<Deduction> + 'static,
+    {
+        Self {
+            id,
+            name,
+            difficulty,
+            requires_uniqueness: false,
+            solve_fn: Box::new(solve_fn),
+        }
+    }
+
+    /// Marks the strategy as relying on the puzzle having a single solution.
+    pub fn requiring_uniqueness(mut self) -> Self {
+        self.requires_uniqueness = true;
+        self
+    }
+}
+
+impl Strategy for FnStrategy {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn difficulty(&self) -> f32 {
+        self.difficulty
+    }
+
+    fn find_deductions(&self, grid: &SudokuGrid) -> Vec<Deduction> {
+        (self.solve_fn)(grid)
+    }
+
+    fn requires_uniqueness(&self) -> bool {
+        self.requires_uniqueness
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+
+const ALL_DIGITS: u16 = 0b11_1111_1110;
+
+/// A set of the digits 1 to 9, stored as a bitmask where bit `n` stands for digit `n`.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Candidates(u16);
+
+impl Candidates {
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn all() -> Self {
+        Self(ALL_DIGITS)
+    }
+
+    /// Returns an empty set for anything that is not a digit from 1 to 9.
+    pub fn single(digit: usize) -> Self {
+        match digit {
+            1..=9 => Self(1 << digit),
+            _ => Self::empty(),
+        }
+    }
+
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, digit: usize) -> bool {
+        self.0 & Self::single(digit).0 != 0
+    }
+
+    pub fn insert(&mut self, digit: usize) {
+        self.0 |= Self::single(digit).0
+    }
+
+    pub fn remove(&mut self, digit: usize) {
+        self.0 &= !Self::single(digit).0
+    }
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    pub const fn is_subset(&self, other: &Self) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// The smallest digit of the set.
+    pub fn first(&self) -> Option<usize> {
+        (!self.is_empty()).then(|| self.0.trailing_zeros() as usize)
+    }
+
+    /// Iterates the digits in ascending order.
+    pub fn iter(&self) -> CandidatesIter {
+        CandidatesIter(self.0)
+    }
+}
+
+pub struct CandidatesIter(u16);
+
+impl Iterator for CandidatesIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let digit = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CandidatesIter {}
+
+impl IntoIterator for Candidates {
+    type Item = usize;
+    type IntoIter = CandidatesIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<usize> for Candidates {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut candidates = Self::empty();
+        for digit in iter {
+            candidates.insert(digit);
+        }
+        candidates
+    }
+}
+
+impl BitOr for Candidates {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for Candidates {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs)
+    }
+}
+
+impl BitAnd for Candidates {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl BitAndAssign for Candidates {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = self.intersection(rhs)
+    }
+}
+
+impl Sub for Candidates {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl SubAssign for Candidates {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.difference(rhs)
+    }
+}
+
+impl fmt::Debug for Candidates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Candidates;
+
+    #[test]
+    fn it_supports_set_operations() {
+        let a = Candidates::from_iter([1, 2, 3]);
+        let b = Candidates::from_iter([3, 4]);
+
+        assert_eq!(Candidates::from_iter([1, 2, 3, 4]), a | b);
+        assert_eq!(Candidates::single(3), a & b);
+        assert_eq!(Candidates::from_iter([1, 2]), a - b);
+        assert_eq!(3, a.len());
+        assert!(Candidates::from_iter([1, 3]).is_subset(&a));
+        assert!(!b.is_subset(&a));
+    }
+
+    #[test]
+    fn it_iterates_digits_in_order() {
+        let mut candidates = Candidates::all();
+        candidates.remove(5);
+        candidates.remove(0);
+        candidates.insert(10);
+
+        assert_eq!(
+            vec![1, 2, 3, 4, 6, 7, 8, 9],
+            candidates.iter().collect::<Vec<usize>>()
+        );
+        assert_eq!(Some(1), candidates.first());
+        assert!(!candidates.contains(5));
+        assert_eq!("[1, 2, 3, 4, 6, 7, 8, 9]", format!("{candidates:?}"));
+    }
+}
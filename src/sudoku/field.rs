@@ -1,11 +1,15 @@
+use std::fmt;
+
+use super::candidates::Candidates;
+
 #[derive(Default, Clone, Debug)]
 pub struct Field {
     value: Option<usize>,
-    possibilities: Vec<usize>,
+    possibilities: Candidates,
     position: FieldPosition,
 }
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FieldPosition {
     row: usize,
     column: usize,
@@ -23,6 +27,24 @@ impl FieldPosition {
     pub fn column(&self) -> usize {
         self.column
     }
+
+    pub fn box_id(&self) -> usize {
+        (self.row / 3) * 3 + self.column / 3
+    }
+
+    /// Whether both positions share a row, column or box. A position does not see itself.
+    pub fn sees(&self, other: &FieldPosition) -> bool {
+        self != other
+            && (self.row == other.row
+                || self.column == other.column
+                || self.box_id() == other.box_id())
+    }
+}
+
+impl fmt::Display for FieldPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} / {}", self.row, self.column)
+    }
 }
 
 impl Field {
@@ -34,16 +56,24 @@ impl Field {
         self.value
     }
 
-    pub fn possibilities(&self) -> &Vec<usize> {
-        &self.possibilities
+    pub fn possibilities(&self) -> Candidates {
+        self.possibilities
     }
 
     pub fn set_value(&mut self, value: usize) {
         self.value = Some(value)
     }
 
+    pub fn clear_value(&mut self) {
+        self.value = None
+    }
+
     pub fn remove_possibility(&mut self, possibility: usize) {
-        self.possibilities.retain(|p| *p != possibility)
+        self.possibilities.remove(possibility)
+    }
+
+    pub fn add_possibility(&mut self, possibility: usize) {
+        self.possibilities.insert(possibility)
     }
 
     pub fn is_filled(&self) -> bool {
@@ -52,7 +82,7 @@ impl Field {
 
     pub fn empty(position: FieldPosition) -> Self {
         Self {
-            possibilities: vec![1, 2, 3, 4, 5, 6, 7, 8, 9],
+            possibilities: Candidates::all(),
             value: None,
             position,
         }
@@ -61,7 +91,7 @@ impl Field {
     pub fn filled(value: usize, position: FieldPosition) -> Self {
         Self {
             value: Some(value),
-            possibilities: vec![],
+            possibilities: Candidates::empty(),
             position,
         }
     }
@@ -1,6 +1,8 @@
-use std::{fs, path::PathBuf};
+use std::fmt;
 
+use super::candidates::Candidates;
 use super::field::{Field, FieldPosition};
+use super::house::House;
 
 #[derive(Default, Clone, Debug)]
 pub struct SudokuGrid {
@@ -8,6 +10,21 @@ pub struct SudokuGrid {
 }
 
 impl SudokuGrid {
+    pub(crate) fn from_rows(rows: Vec<Vec<Field>>) -> Self {
+        Self { rows }
+    }
+
+    /// All values row by row on a single line, `.` marks an empty field.
+    pub fn to_line(&self) -> String {
+        self.fields()
+            .iter()
+            .map(|f| match f.value() {
+                None => ".".to_string(),
+                Some(v) => v.to_string(),
+            })
+            .collect()
+    }
+
     pub fn fields(&self) -> Vec<&Field> {
         self.rows.iter().flatten().collect()
     }
@@ -28,13 +45,46 @@ impl SudokuGrid {
         row.get_mut(position.column())
     }
 
-    pub fn is_completed(&self) -> bool {
-        self.rows.iter().all(|row| {
-            let mut row_values = row.iter().filter_map(|f| f.value()).collect::<Vec<usize>>();
-            row_values.sort();
+    /// Empties the field and brings back the candidates its value ruled out: the field gets every
+    /// digit none of its peers holds, peers that see the value nowhere else get it back.
+    pub fn clear_value(&mut self, position: &FieldPosition) {
+        let Some(value) = self.get_field(position.clone()).and_then(|f| f.value()) else {
+            return;
+        };
+        if let Some(field) = self.get_field_mut(position) {
+            field.clear_value();
+        }
+
+        let possibilities = Candidates::all() - self.peer_values(position);
+        if let Some(field) = self.get_field_mut(position) {
+            for digit in 1..=9 {
+                match possibilities.contains(digit) {
+                    true => field.add_possibility(digit),
+                    false => field.remove_possibility(digit),
+                }
+            }
+        }
+
+        let peers = self
+            .get_peers(position)
+            .into_iter()
+            .filter(|f| !f.is_filled())
+            .map(|f| f.position().clone())
+            .collect::<Vec<FieldPosition>>();
+        for peer in peers {
+            if !self.peer_values(&peer).contains(value) {
+                if let Some(field) = self.get_field_mut(&peer) {
+                    field.add_possibility(value);
+                }
+            }
+        }
+    }
 
-            row_values.eq(&vec![1, 2, 3, 4, 5, 6, 7, 8, 9])
-        })
+    fn peer_values(&self, position: &FieldPosition) -> Candidates {
+        self.get_peers(position)
+            .into_iter()
+            .filter_map(|f| f.value())
+            .collect()
     }
 
     pub fn get_fields_in_row(&self, row_idx: usize) -> Option<&Vec<Field>> {
@@ -74,6 +124,22 @@ impl SudokuGrid {
         }
     }
 
+    pub fn get_fields_in_house(&self, house: House) -> Vec<&Field> {
+        house
+            .positions()
+            .into_iter()
+            .filter_map(|position| self.get_field(position))
+            .collect()
+    }
+
+    /// The 20 fields that share a row, column or box with the given position.
+    pub fn get_peers(&self, position: &FieldPosition) -> Vec<&Field> {
+        self.fields()
+            .into_iter()
+            .filter(|f| f.position().sees(position))
+            .collect()
+    }
+
     pub fn get_fields_in_box(&self, box_id: usize) -> Vec<&Field> {
         self.get_fields_in_box_with_positions(box_id)
             .into_iter()
@@ -129,47 +195,60 @@ impl SudokuGrid {
     }
 }
 
-impl TryFrom<PathBuf> for SudokuGrid {
-    type Error = String;
-
-    fn try_from(value: PathBuf) -> Result<Self, Self::Error> {
-        let contents = &fs::read_to_string(value).unwrap();
-        let lines = contents
-            .lines()
-            .filter(|l| !l.is_empty())
-            .collect::<Vec<&str>>();
-
-        if lines.len() != 9 {
-            return Err(String::from(
-                "File has more than 9 lines, can not create valid sudoku",
-            ));
+#[cfg(test)]
+impl SudokuGrid {
+    /// An empty grid where the given fields only have the given candidates left, the fixture of
+    /// most strategy tests.
+    pub(crate) fn with_possibilities(fields: &[((usize, usize), &[usize])]) -> Self {
+        let mut grid = SudokuGrid::from([[0; 9]; 9]);
+        for ((row, column), possibilities) in fields {
+            if let Some(field) = grid.get_field_mut(&FieldPosition::new(*row, *column)) {
+                for digit in (1..=9).filter(|d| !possibilities.contains(d)) {
+                    field.remove_possibility(digit);
+                }
+            }
         }
+        grid
+    }
+}
 
-        let rows: Vec<Vec<Field>> = lines
+impl From<[[usize; 9]; 9]> for SudokuGrid {
+    /// Builds a grid from plain values where `0` marks an empty field.
+    fn from(values: [[usize; 9]; 9]) -> Self {
+        let rows = values
             .iter()
             .enumerate()
-            .map(|(row_idx, line)| {
-                let fields = line
-                    .trim()
-                    .split(' ')
+            .map(|(row_idx, row)| {
+                row.iter()
                     .enumerate()
-                    .map(|(col_idx, c)| match c.parse::<usize>() {
-                        Ok(value) => match value {
-                            1..=9 => Field::filled(value, FieldPosition::new(row_idx, col_idx)),
-                            _ => Field::empty(FieldPosition::new(row_idx, col_idx)),
-                        },
+                    .map(|(col_idx, value)| match value {
+                        1..=9 => Field::filled(*value, FieldPosition::new(row_idx, col_idx)),
                         _ => Field::empty(FieldPosition::new(row_idx, col_idx)),
                     })
-                    .collect::<Vec<Field>>();
+                    .collect()
+            })
+            .collect();
 
-                if fields.len() != 9 {
-                    panic!("Row has more than 9 fields, can not create valid sudoku");
-                }
+        SudokuGrid { rows }
+    }
+}
 
-                fields
+impl fmt::Display for SudokuGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|f| match f.value() {
+                        None => "x".to_string(),
+                        Some(v) => v.to_string(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ")
             })
-            .collect();
+            .collect::<Vec<String>>();
 
-        Ok(SudokuGrid { rows })
+        write!(f, "{}", lines.join("\n"))
     }
 }
@@ -0,0 +1,59 @@
+use std::fmt;
+
+use super::field::FieldPosition;
+
+/// One of the 27 rows, columns and boxes which have to contain every digit exactly once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum House {
+    Row(usize),
+    Column(usize),
+    Box(usize),
+}
+
+impl House {
+    pub fn rows() -> impl Iterator<Item = House> {
+        (0..9).map(House::Row)
+    }
+
+    pub fn columns() -> impl Iterator<Item = House> {
+        (0..9).map(House::Column)
+    }
+
+    pub fn boxes() -> impl Iterator<Item = House> {
+        (0..9).map(House::Box)
+    }
+
+    pub fn all() -> impl Iterator<Item = House> {
+        Self::rows().chain(Self::columns()).chain(Self::boxes())
+    }
+
+    pub fn positions(&self) -> Vec<FieldPosition> {
+        (0..9)
+            .map(|i| match self {
+                House::Row(row) => FieldPosition::new(*row, i),
+                House::Column(column) => FieldPosition::new(i, *column),
+                House::Box(box_id) => {
+                    FieldPosition::new((box_id / 3) * 3 + i / 3, (box_id % 3) * 3 + i % 3)
+                }
+            })
+            .collect()
+    }
+
+    pub fn contains(&self, position: &FieldPosition) -> bool {
+        match self {
+            House::Row(row) => position.row() == *row,
+            House::Column(column) => position.column() == *column,
+            House::Box(box_id) => position.box_id() == *box_id,
+        }
+    }
+}
+
+impl fmt::Display for House {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            House::Row(row) => write!(f, "row {row}"),
+            House::Column(column) => write!(f, "column {column}"),
+            House::Box(box_id) => write!(f, "box {box_id}"),
+        }
+    }
+}
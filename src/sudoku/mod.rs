@@ -1,2 +1,6 @@
+pub mod candidates;
 pub mod field;
 pub mod grid;
+pub mod house;
+pub mod parse;
+pub mod validation;
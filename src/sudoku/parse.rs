@@ -0,0 +1,278 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::{error, fmt};
+
+use super::field::{Field, FieldPosition};
+use super::grid::SudokuGrid;
+
+/// Why a grid could not be read. Lines and columns count from 1.
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    /// The input does not have 9 non-empty lines.
+    RowCount(usize),
+    /// A line does not have 9 fields.
+    CellCount {
+        line: usize,
+        count: usize,
+    },
+    /// A grid on a single line does not have 81 fields.
+    LineLength {
+        line: usize,
+        count: usize,
+    },
+    /// A field is neither a digit nor a placeholder for an empty field.
+    InvalidToken {
+        line: usize,
+        column: usize,
+        token: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(e) => write!(f, "Could not read the grid: {e}"),
+            ParseError::RowCount(count) => write!(f, "Expected 9 rows, found {count}"),
+            ParseError::CellCount { line, count } => {
+                write!(f, "Expected 9 fields in line {line}, found {count}")
+            }
+            ParseError::LineLength { line, count } => {
+                write!(f, "Expected 81 fields in line {line}, found {count}")
+            }
+            ParseError::InvalidToken {
+                line,
+                column,
+                token,
+            } => write!(
+                f,
+                "Invalid field \"{token}\" in line {line}, column {column}, expected 1 to 9, or x, . or 0 for an empty field"
+            ),
+        }
+    }
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(e: io::Error) -> Self {
+        ParseError::Io(e)
+    }
+}
+
+impl SudokuGrid {
+    /// Reads either 9 lines of 9 fields separated by whitespace or a single line of 81 fields
+    /// without separators. A field is a digit from 1 to 9 or one of `x`, `.` and `0` for an
+    /// empty field. Empty lines are skipped.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .collect::<Vec<(usize, &str)>>();
+
+        // a single line with separators is a row of a grid rather than a whole grid
+        if let [(line_idx, line)] = lines[..] {
+            if line.split_whitespace().nth(1).is_none() {
+                return parse_line(line_idx + 1, line);
+            }
+        }
+
+        if lines.len() != 9 {
+            return Err(ParseError::RowCount(lines.len()));
+        }
+
+        let rows = lines
+            .into_iter()
+            .enumerate()
+            .map(|(row_idx, (line_idx, line))| parse_row(row_idx, line_idx + 1, line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(SudokuGrid::from_rows(rows))
+    }
+
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, ParseError> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+
+        Self::parse(&input)
+    }
+}
+
+/// Reads a whole grid from a single line like `..2685..4...`, the fields go row by row.
+fn parse_line(line: usize, text: &str) -> Result<SudokuGrid, ParseError> {
+    let trimmed = text.trim();
+    let tokens = trimmed
+        .char_indices()
+        .map(|(offset, c)| &trimmed[offset..offset + c.len_utf8()])
+        .collect::<Vec<&str>>();
+    if tokens.len() != 81 {
+        return Err(ParseError::LineLength {
+            line,
+            count: tokens.len(),
+        });
+    }
+
+    let fields = tokens
+        .into_iter()
+        .enumerate()
+        .map(|(idx, token)| {
+            parse_field(FieldPosition::new(idx / 9, idx % 9), token).ok_or_else(|| {
+                ParseError::InvalidToken {
+                    line,
+                    column: column_of(text, token),
+                    token: token.to_string(),
+                }
+            })
+        })
+        .collect::<Result<Vec<Field>, _>>()?;
+
+    Ok(SudokuGrid::from_rows(
+        fields.chunks(9).map(|row| row.to_vec()).collect(),
+    ))
+}
+
+fn parse_row(row_idx: usize, line: usize, text: &str) -> Result<Vec<Field>, ParseError> {
+    let tokens = text.split_whitespace().collect::<Vec<&str>>();
+    if tokens.len() != 9 {
+        return Err(ParseError::CellCount {
+            line,
+            count: tokens.len(),
+        });
+    }
+
+    tokens
+        .into_iter()
+        .enumerate()
+        .map(|(col_idx, token)| {
+            parse_field(FieldPosition::new(row_idx, col_idx), token).ok_or_else(|| {
+                ParseError::InvalidToken {
+                    line,
+                    column: column_of(text, token),
+                    token: token.to_string(),
+                }
+            })
+        })
+        .collect()
+}
+
+fn parse_field(position: FieldPosition, token: &str) -> Option<Field> {
+    match token {
+        "x" | "X" | "." | "0" => Some(Field::empty(position)),
+        _ => match token.parse::<usize>() {
+            Ok(value @ 1..=9) => Some(Field::filled(value, position)),
+            _ => None,
+        },
+    }
+}
+
+/// The column the token starts at, `token` has to be a slice of `text`.
+fn column_of(text: &str, token: &str) -> usize {
+    let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+    text[..offset].chars().count() + 1
+}
+
+impl FromStr for SudokuGrid {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<PathBuf> for SudokuGrid {
+    type Error = ParseError;
+
+    fn try_from(value: PathBuf) -> Result<Self, Self::Error> {
+        Self::from_reader(File::open(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+    use crate::sudoku::grid::SudokuGrid;
+
+    const GRID: &str = "x x 2 6 8 5 x x 4
+5 8 x x 3 4 2 6 1
+6 x 4 2 1 x 8 3 5
+x 6 x x x x x 5 2
+x 2 x 5 x x 3 1 x
+9 x 5 x x 2 x 8 x
+8 x 1 x 2 6 5 4 3
+2 5 6 4 x 3 1 x 8
+x 4 x 8 5 1 6 2 x";
+
+    #[test]
+    fn it_parses_a_grid_from_a_reader() {
+        let grid = SudokuGrid::from_reader(GRID.as_bytes()).unwrap();
+
+        assert_eq!(GRID, grid.to_string());
+    }
+
+    #[test]
+    fn it_reports_where_the_input_is_wrong() {
+        let too_short = GRID.lines().take(8).collect::<Vec<&str>>().join("\n");
+        assert!(matches!(
+            SudokuGrid::parse(&too_short),
+            Err(ParseError::RowCount(8))
+        ));
+        assert!(matches!(
+            SudokuGrid::parse(GRID.lines().next().unwrap()),
+            Err(ParseError::RowCount(1))
+        ));
+
+        let missing_field = GRID.replacen("5 8 x x", "5 8 x", 1);
+        assert!(matches!(
+            SudokuGrid::parse(&missing_field),
+            Err(ParseError::CellCount { line: 2, count: 8 })
+        ));
+
+        let invalid = GRID.replacen("6 x 4 2", "6 x 4 a", 1);
+        let error = SudokuGrid::parse(&invalid).unwrap_err();
+        assert_eq!(
+            "Invalid field \"a\" in line 3, column 7, expected 1 to 9, or x, . or 0 for an empty field",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn it_reads_and_writes_the_single_line_format() {
+        let line = GRID
+            .split_whitespace()
+            .collect::<String>()
+            .replace('x', ".");
+        let grid = SudokuGrid::parse(&format!("{line}\n")).unwrap();
+
+        assert_eq!(GRID, grid.to_string());
+        assert_eq!(line, grid.to_line());
+        assert_eq!(
+            GRID,
+            SudokuGrid::parse(&line.replace('.', "0"))
+                .unwrap()
+                .to_string()
+        );
+
+        assert!(matches!(
+            SudokuGrid::parse(&line[1..]),
+            Err(ParseError::LineLength { line: 1, count: 80 })
+        ));
+        assert!(matches!(
+            SudokuGrid::parse(&line.replacen('.', "-", 1)),
+            Err(ParseError::InvalidToken {
+                line: 1,
+                column: 1,
+                ..
+            })
+        ));
+    }
+}
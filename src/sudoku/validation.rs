@@ -0,0 +1,182 @@
+use std::fmt;
+
+use super::candidates::Candidates;
+use super::field::FieldPosition;
+use super::grid::SudokuGrid;
+use super::house::House;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Validity {
+    /// Every field is filled and every house holds each digit once.
+    Solved,
+    /// Every field is filled, but some house holds a digit twice.
+    CompleteWithErrors,
+    /// Some fields are still empty.
+    Incomplete,
+}
+
+impl fmt::Display for Validity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Validity::Solved => write!(f, "Solved"),
+            Validity::CompleteWithErrors => write!(f, "Complete with errors"),
+            Validity::Incomplete => write!(f, "Incomplete"),
+        }
+    }
+}
+
+/// Everything that makes a grid impossible to solve at first glance.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Conflicts {
+    /// Fields that see each other and hold the same value, every pair once.
+    pub duplicates: Vec<(FieldPosition, FieldPosition)>,
+    /// Empty fields whose candidates are all placed in their peers already.
+    pub without_candidates: Vec<FieldPosition>,
+}
+
+impl Conflicts {
+    pub fn is_empty(&self) -> bool {
+        self.duplicates.is_empty() && self.without_candidates.is_empty()
+    }
+
+    /// Every field involved in a conflict, each once.
+    pub fn positions(&self) -> Vec<FieldPosition> {
+        let mut positions = self.without_candidates.clone();
+        for (a, b) in self.duplicates.iter() {
+            for position in [a, b] {
+                if !positions.contains(position) {
+                    positions.push(position.clone());
+                }
+            }
+        }
+
+        positions
+    }
+}
+
+impl SudokuGrid {
+    /// Checks all 27 houses of a filled grid.
+    pub fn validate(&self) -> Validity {
+        if self.fields().iter().any(|f| !f.is_filled()) {
+            return Validity::Incomplete;
+        }
+
+        let valid = House::all().all(|house| {
+            let values = self
+                .get_fields_in_house(house)
+                .iter()
+                .filter_map(|f| f.value())
+                .collect::<Candidates>();
+            values == Candidates::all()
+        });
+
+        match valid {
+            true => Validity::Solved,
+            false => Validity::CompleteWithErrors,
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.validate() == Validity::Solved
+    }
+
+    pub fn find_conflicts(&self) -> Conflicts {
+        let fields = self.fields();
+
+        let duplicates = fields
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| {
+                fields[i + 1..]
+                    .iter()
+                    .filter(move |b| {
+                        a.value().is_some()
+                            && a.value() == b.value()
+                            && a.position().sees(b.position())
+                    })
+                    .map(move |b| (a.position().clone(), b.position().clone()))
+            })
+            .collect();
+
+        let without_candidates = fields
+            .iter()
+            .filter(|f| !f.is_filled())
+            .filter(|f| {
+                let values = self
+                    .get_peers(f.position())
+                    .into_iter()
+                    .filter_map(|peer| peer.value())
+                    .collect::<Candidates>();
+                (f.possibilities() - values).is_empty()
+            })
+            .map(|f| f.position().clone())
+            .collect();
+
+        Conflicts {
+            duplicates,
+            without_candidates,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Conflicts, Validity};
+    use crate::solver::SolveStep;
+    use crate::sudoku::candidates::Candidates;
+    use crate::sudoku::field::FieldPosition;
+    use crate::sudoku::grid::SudokuGrid;
+
+    #[test]
+    fn it_finds_no_conflicts_in_a_valid_grid() {
+        let grid = SudokuGrid::try_from(PathBuf::from("data/grid.txt")).unwrap();
+
+        assert_eq!(Conflicts::default(), grid.find_conflicts());
+    }
+
+    #[test]
+    fn it_finds_duplicate_values_and_fields_without_candidates() {
+        let mut values = [[0; 9]; 9];
+        values[0][0] = 5;
+        values[0][8] = 5;
+        values[4][4] = 1;
+        let mut grid = SudokuGrid::from(values);
+        grid.apply_solve_steps(&vec![(
+            FieldPosition::new(4, 0),
+            SolveStep::RemovePossibilities(Candidates::all() - Candidates::single(1)),
+        )]);
+
+        let conflicts = grid.find_conflicts();
+
+        assert_eq!(
+            vec![(FieldPosition::new(0, 0), FieldPosition::new(0, 8))],
+            conflicts.duplicates
+        );
+        assert_eq!(vec![FieldPosition::new(4, 0)], conflicts.without_candidates);
+    }
+
+    #[test]
+    fn it_checks_every_house_of_a_filled_grid() {
+        let solution: [[usize; 9]; 9] =
+            std::array::from_fn(|r| std::array::from_fn(|c| (r * 3 + r / 3 + c) % 9 + 1));
+        assert_eq!(Validity::Solved, SudokuGrid::from(solution).validate());
+
+        // every row stays a permutation, but the columns and boxes do not
+        let mut rows_only = solution;
+        rows_only[1] = rows_only[0];
+        assert_eq!(
+            Validity::CompleteWithErrors,
+            SudokuGrid::from(rows_only).validate()
+        );
+
+        let mut incomplete = solution;
+        incomplete[4][4] = 0;
+        assert_eq!(
+            Validity::Incomplete,
+            SudokuGrid::from(incomplete).validate()
+        );
+        assert!(!SudokuGrid::from(incomplete).is_solved());
+    }
+}
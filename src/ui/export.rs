@@ -1,24 +1,37 @@
 use crate::ui::SudokuUi;
-use std::{fs, path::PathBuf};
+use std::{fmt, fs, path::PathBuf};
+
+/// How an exported grid is written to the file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Nine rows of space separated fields, `x` marks an empty field.
+    #[default]
+    Rows,
+    /// All 81 fields on a single line, `.` marks an empty field.
+    Line,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 2] = [ExportFormat::Rows, ExportFormat::Line];
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportFormat::Rows => write!(f, "Nine rows"),
+            ExportFormat::Line => write!(f, "Single line"),
+        }
+    }
+}
 
 impl SudokuUi {
-    pub fn export_to(&self, target: &PathBuf) {
+    pub fn export_to(&self, target: &PathBuf, format: ExportFormat) {
         if let Some(grid) = self.grid() {
-            let export = grid
-                .grid
-                .rows()
-                .iter()
-                .map(|row| {
-                    row.iter()
-                        .map(|f| match f.value() {
-                            None => "x".to_string(),
-                            Some(v) => v.to_string(),
-                        })
-                        .collect::<Vec<String>>()
-                        .join(" ")
-                })
-                .collect::<Vec<String>>()
-                .join("\r\n");
+            let export = match format {
+                // the files have always been written with Windows line endings
+                ExportFormat::Rows => grid.grid.to_string().replace('\n', "\r\n"),
+                ExportFormat::Line => grid.grid.to_line(),
+            };
             let _ = fs::write(target, export.as_bytes());
         }
     }
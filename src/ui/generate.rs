@@ -0,0 +1,156 @@
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::generator::{GeneratorOptions, PuzzleGenerator, Symmetry};
+use crate::solver::grader::Difficulty;
+use crate::sudoku::grid::SudokuGrid;
+use crate::ui::SudokuUi;
+use eframe::egui;
+
+/// A puzzle generated in the background, hitting a difficulty can take many attempts.
+pub struct Generation {
+    options: GeneratorOptions,
+    receiver: Receiver<Option<SudokuGrid>>,
+}
+
+impl Generation {
+    fn start(options: GeneratorOptions) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(PuzzleGenerator::new(options).generate());
+        });
+
+        Self { options, receiver }
+    }
+
+    fn failure(&self) -> String {
+        match self.options.difficulty {
+            Some(difficulty) => format!(
+                "Could not reach {difficulty} within {} attempts, try again",
+                self.options.max_attempts
+            ),
+            None => "Could not generate a puzzle with these options".to_string(),
+        }
+    }
+}
+
+impl SudokuUi {
+    /// Starts generating a puzzle with the current options, unless one is on its way already.
+    pub fn new_puzzle(&mut self) {
+        if self.generation.is_none() {
+            self.generation = Some(Generation::start(self.generator_options));
+        }
+    }
+
+    /// Shows the generated puzzle once it is done, or why there is none.
+    pub fn receive_puzzle(&mut self) {
+        let Some(generation) = &self.generation else {
+            return;
+        };
+
+        let result = match generation.receiver.try_recv() {
+            Err(TryRecvError::Empty) => return,
+            Ok(Some(grid)) => Ok(grid),
+            Ok(None) => Err(generation.failure()),
+            Err(TryRecvError::Disconnected) => Err("Generating the puzzle failed".to_string()),
+        };
+
+        self.generation = None;
+        match result {
+            Ok(grid) => self.set_grid(grid),
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    pub fn generate_ui(&mut self, ui: &mut egui::Ui) {
+        let generating = self.generation.is_some();
+        if ui
+            .add_enabled(!generating, egui::Button::new("New puzzle"))
+            .clicked()
+        {
+            self.new_puzzle();
+        }
+
+        if generating {
+            ui.spinner();
+        }
+    }
+
+    pub fn generator_options_ui(&mut self, ui: &mut egui::Ui) {
+        let options = &mut self.generator_options;
+
+        egui::ComboBox::from_label("Symmetry")
+            .selected_text(options.symmetry.to_string())
+            .show_ui(ui, |ui| {
+                for symmetry in Symmetry::ALL {
+                    ui.selectable_value(&mut options.symmetry, symmetry, symmetry.to_string());
+                }
+            });
+
+        egui::ComboBox::from_label("Difficulty")
+            .selected_text(
+                options
+                    .difficulty
+                    .map_or("any".to_string(), |d| d.to_string()),
+            )
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut options.difficulty, None, "any");
+                for difficulty in Difficulty::ALL {
+                    ui.selectable_value(
+                        &mut options.difficulty,
+                        Some(difficulty),
+                        difficulty.to_string(),
+                    );
+                }
+            });
+
+        ui.add(egui::Slider::new(&mut options.clues, 17..=81).text("Minimum clues"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::generator::GeneratorOptions;
+    use crate::solver::grader::Difficulty;
+    use crate::ui::SudokuUi;
+
+    fn wait_for_puzzle(ui: &mut SudokuUi) {
+        while ui.generation.is_some() {
+            thread::sleep(Duration::from_millis(10));
+            ui.receive_puzzle();
+        }
+    }
+
+    #[test]
+    fn it_generates_puzzles_in_the_background() {
+        let mut ui = SudokuUi::new(None);
+
+        ui.new_puzzle();
+        wait_for_puzzle(&mut ui);
+
+        assert!(ui.grid().is_some());
+        assert_eq!(None, ui.error);
+    }
+
+    #[test]
+    fn it_tells_when_the_difficulty_was_not_reached() {
+        let mut ui = SudokuUi::new(None);
+        ui.generator_options = GeneratorOptions {
+            difficulty: Some(Difficulty::Expert),
+            max_attempts: 0,
+            ..Default::default()
+        };
+
+        ui.new_puzzle();
+        wait_for_puzzle(&mut ui);
+
+        assert!(ui.grid().is_none());
+        assert_eq!(
+            Some("Could not reach expert within 0 attempts, try again".to_string()),
+            ui.error
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::solver::{Deduction, SolveStep};
+use crate::sudoku::field::FieldPosition;
+use crate::ui::history::HistoryEntry;
+use crate::ui::SudokuUi;
+use eframe::egui;
+use egui::Color32;
+
+/// How much of a hint is revealed, every stage includes the ones before.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HintStage {
+    Technique,
+    Region,
+    Step,
+}
+
+#[derive(Clone, Debug)]
+pub struct Hint {
+    deduction: Deduction,
+    stage: HintStage,
+}
+
+impl Hint {
+    pub fn new(deduction: Deduction) -> Self {
+        Self {
+            deduction,
+            stage: HintStage::Technique,
+        }
+    }
+
+    pub fn stage(&self) -> HintStage {
+        self.stage
+    }
+
+    pub fn reveal_more(&mut self) {
+        self.stage = match self.stage {
+            HintStage::Technique => HintStage::Region,
+            HintStage::Region | HintStage::Step => HintStage::Step,
+        };
+    }
+
+    pub fn text(&self) -> String {
+        match self.stage {
+            HintStage::Technique => format!("Try {}", self.deduction.strategy),
+            HintStage::Region => {
+                format!("Try {} on the highlighted fields", self.deduction.strategy)
+            }
+            HintStage::Step => self.deduction.to_string(),
+        }
+    }
+
+    /// The fields to color for the revealed stages, later entries win.
+    pub fn highlights(&self) -> Vec<(FieldPosition, Color32)> {
+        let mut highlights = vec![];
+        if self.stage == HintStage::Technique {
+            return highlights;
+        }
+
+        for house in self
+            .deduction
+            .base_houses
+            .iter()
+            .chain(self.deduction.cover_houses.iter())
+        {
+            highlights.extend(
+                house
+                    .positions()
+                    .into_iter()
+                    .map(|p| (p, Color32::LIGHT_YELLOW)),
+            );
+        }
+        highlights.extend(
+            self.deduction
+                .justification
+                .iter()
+                .map(|(p, _)| (p.clone(), Color32::YELLOW)),
+        );
+
+        if self.stage == HintStage::Step {
+            highlights.extend(self.deduction.solve_steps.iter().map(|(p, step)| {
+                let color = match step {
+                    SolveStep::SetValue(_) => Color32::GREEN,
+                    SolveStep::RemovePossibilities(_) => Color32::RED,
+                };
+                (p.clone(), color)
+            }));
+        }
+
+        highlights
+    }
+}
+
+impl SudokuUi {
+    /// Asks the solver for the easiest step on the first call, reveals more of it afterwards.
+    pub fn hint(&mut self) -> Result<(), String> {
+        if let Some(hint) = self.hint.as_mut() {
+            hint.reveal_more();
+            return Ok(());
+        }
+
+        let grid = self.grid().ok_or("There is no puzzle to give a hint for")?;
+        if grid.grid.is_solved() {
+            return Err("There is nothing left to do".to_string());
+        }
+        let deduction = self
+            .solver
+            .determine_hint(&grid.grid)
+            .ok_or("No logical step found")?;
+        self.hint = Some(Hint::new(deduction));
+
+        Ok(())
+    }
+
+    /// Applies the step of the hint to the grid.
+    pub fn accept_hint(&mut self) {
+        let Some(hint) = self.hint.take() else {
+            return;
+        };
+
+        self.add_history_entry(HistoryEntry::Solver(hint.deduction));
+    }
+
+    pub fn hint_ui(&mut self, ui: &mut egui::Ui) {
+        let Some(hint) = &self.hint else {
+            return;
+        };
+
+        ui.label(hint.text());
+        let fully_revealed = hint.stage() == HintStage::Step;
+        ui.horizontal(|ui| {
+            if fully_revealed {
+                if ui.button("Apply").clicked() {
+                    self.accept_hint();
+                }
+            } else if ui.button("Reveal more").clicked() {
+                let _ = self.hint();
+            }
+
+            if ui.button("Dismiss").clicked() {
+                self.hint = None;
+            }
+        });
+        ui.separator();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::solver::{SolutionCount, SudokuSolver, BACKTRACKING};
+    use crate::sudoku::grid::SudokuGrid;
+    use crate::ui::SudokuUi;
+
+    #[test]
+    fn it_does_not_reveal_the_solution_once_the_strategies_are_stuck() {
+        let mut grid = SudokuGrid::try_from(PathBuf::from("data/grid13.txt")).unwrap();
+        let solver = SudokuSolver::new();
+        while let Some(deduction) = solver.determine_hint(&grid) {
+            grid.apply_deductions(&[deduction]);
+        }
+        assert!(!grid.is_solved());
+        assert_eq!(
+            Some(BACKTRACKING),
+            solver
+                .determine_deductions(&grid)
+                .first()
+                .map(|d| d.strategy)
+        );
+
+        let mut ui = SudokuUi::new(Some(grid));
+
+        assert_eq!(Err("No logical step found".to_string()), ui.hint());
+        assert!(ui.hint.is_none());
+    }
+
+    #[test]
+    fn it_does_not_hint_uniqueness_techniques_for_puzzles_with_several_solutions() {
+        // a unique rectangle type 1 on an otherwise empty grid
+        let grid = SudokuGrid::with_possibilities(&[
+            ((0, 0), &[1, 2]),
+            ((0, 3), &[1, 2]),
+            ((1, 0), &[1, 2]),
+            ((1, 3), &[1, 2, 5]),
+        ]);
+        let mut ui = SudokuUi::new(Some(grid));
+        let others = ui
+            .solver
+            .strategies()
+            .filter(|s| !s.requires_uniqueness())
+            .map(|s| s.id())
+            .collect::<Vec<&str>>();
+        for id in others {
+            ui.solver.set_enabled(id, false);
+        }
+
+        assert_eq!(Some(SolutionCount::Multiple), ui.solution_count);
+        assert_eq!(Err("No logical step found".to_string()), ui.hint());
+    }
+}
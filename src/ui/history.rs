@@ -0,0 +1,193 @@
+use std::fmt;
+
+use crate::solver::Deduction;
+use crate::sudoku::field::FieldPosition;
+use crate::ui::pencil_marks::PencilMarks;
+use crate::ui::{SudokuGridWithColoredFields, SudokuUi};
+use eframe::egui;
+
+/// Label of the steps the player made by hand.
+const MANUAL: &str = "Manual";
+
+/// A change the player made to a single field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ManualChange {
+    SetValue(usize),
+    ClearValue,
+    RemovePossibility(usize),
+    AddPossibility(usize),
+    /// Replaces the notes of the player, they do not matter to the solver.
+    SetPencilMarks(PencilMarks),
+}
+
+impl ManualChange {
+    fn apply_to(&self, grid: &mut SudokuGridWithColoredFields, position: &FieldPosition) {
+        match self {
+            ManualChange::SetPencilMarks(pencil_marks) => {
+                if let Some(metadata) = grid.metadata_mut(position) {
+                    metadata.pencil_marks = *pencil_marks;
+                }
+            }
+            // brings back the candidates the value ruled out, not just the field
+            ManualChange::ClearValue => grid.grid.clear_value(position),
+            ManualChange::SetValue(value) => {
+                if let Some(field) = grid.grid.get_field_mut(position) {
+                    field.set_value(*value);
+                }
+            }
+            ManualChange::RemovePossibility(possibility) => {
+                if let Some(field) = grid.grid.get_field_mut(position) {
+                    field.remove_possibility(*possibility);
+                }
+            }
+            ManualChange::AddPossibility(possibility) => {
+                if let Some(field) = grid.grid.get_field_mut(position) {
+                    field.add_possibility(*possibility);
+                }
+            }
+        }
+    }
+
+    fn describe(&self, position: &FieldPosition) -> String {
+        match self {
+            ManualChange::SetValue(value) => format!("set {position} to {value}"),
+            ManualChange::ClearValue => format!("clear {position}"),
+            ManualChange::RemovePossibility(possibility) => {
+                format!("remove {possibility} from {position}")
+            }
+            ManualChange::AddPossibility(possibility) => format!("add {possibility} to {position}"),
+            ManualChange::SetPencilMarks(_) => format!("change the notes of {position}"),
+        }
+    }
+}
+
+/// One step of the history, either found by the solver or made by the player.
+#[derive(Clone, Debug)]
+pub enum HistoryEntry {
+    Solver(Deduction),
+    Manual(Vec<(FieldPosition, ManualChange)>),
+}
+
+impl HistoryEntry {
+    fn apply_to(&self, grid: &mut SudokuGridWithColoredFields) {
+        match self {
+            HistoryEntry::Solver(deduction) => {
+                grid.grid.apply_deductions(std::slice::from_ref(deduction))
+            }
+            HistoryEntry::Manual(changes) => {
+                for (position, change) in changes {
+                    change.apply_to(grid, position);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryEntry::Solver(deduction) => write!(f, "{deduction}"),
+            HistoryEntry::Manual(changes) => {
+                let changes = changes
+                    .iter()
+                    .map(|(position, change)| change.describe(position))
+                    .collect::<Vec<String>>();
+                write!(f, "{MANUAL}: {}.", changes.join("; "))
+            }
+        }
+    }
+}
+
+impl SudokuUi {
+    /// Applies the entry to the grid and records it. Entries that were undone are dropped, as
+    /// they may not fit anymore.
+    pub fn add_history_entry(&mut self, entry: HistoryEntry) {
+        if let Some(grid) = self.grid_mut() {
+            entry.apply_to(grid);
+        }
+
+        self.history.truncate(self.applied_steps);
+        self.history.push(entry);
+        self.applied_steps = self.history.len();
+        self.hint = None;
+        self.update_analysis();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.applied_steps > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.applied_steps < self.history.len()
+    }
+
+    /// Takes back the last applied step, no matter if it was made by hand or by the solver.
+    pub fn undo(&mut self) {
+        if self.can_undo() {
+            self.rewind_to(self.applied_steps - 1);
+        }
+    }
+
+    /// Applies the next step that was undone. Returns whether there was one.
+    pub fn redo(&mut self) -> bool {
+        let Some(entry) = self.history.get(self.applied_steps).cloned() else {
+            return false;
+        };
+
+        if let Some(grid) = self.grid_mut() {
+            entry.apply_to(grid);
+        }
+        self.applied_steps += 1;
+        self.hint = None;
+        self.update_analysis();
+
+        true
+    }
+
+    /// Rebuilds the grid from the initial one with the first `applied_steps` steps of the history.
+    /// Later steps are kept, so they can be redone.
+    pub fn rewind_to(&mut self, applied_steps: usize) {
+        let Some(initial_grid) = self.initial_grid.clone() else {
+            return;
+        };
+
+        let applied_steps = applied_steps.min(self.history.len());
+        let mut grid = SudokuGridWithColoredFields::new(initial_grid);
+        for entry in self.history[..applied_steps].iter() {
+            entry.apply_to(&mut grid);
+        }
+        self.grid = Some(grid);
+
+        self.applied_steps = applied_steps;
+        self.hint = None;
+        self.pause();
+        self.update_analysis();
+    }
+
+    /// Handles Ctrl+Z and Ctrl+Y.
+    pub fn history_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z)) {
+            self.undo();
+        }
+
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y)) {
+            self.redo();
+        }
+    }
+
+    pub fn history_ui(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add_enabled(self.can_undo(), egui::Button::new("Undo"))
+            .clicked()
+        {
+            self.undo();
+        }
+
+        if ui
+            .add_enabled(self.can_redo(), egui::Button::new("Redo"))
+            .clicked()
+        {
+            self.redo();
+        }
+    }
+}
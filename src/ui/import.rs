@@ -1,13 +1,12 @@
 use crate::sudoku::grid::SudokuGrid;
+use crate::sudoku::parse::ParseError;
 use crate::ui::SudokuUi;
 use std::path::Path;
 
-use super::SudokuGridWithColoredFields;
-
 impl SudokuUi {
-    pub fn import_from(&mut self, target: &Path) -> Result<(), String> {
+    pub fn import_from(&mut self, target: &Path) -> Result<(), ParseError> {
         let grid = SudokuGrid::try_from(target.to_path_buf())?;
-        *self.grid_mut() = Some(SudokuGridWithColoredFields::new(grid));
+        self.set_grid(grid);
 
         Ok(())
     }
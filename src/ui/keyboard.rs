@@ -0,0 +1,359 @@
+use crate::sudoku::field::{Field, FieldPosition};
+use crate::ui::history::ManualChange;
+use crate::ui::pencil_marks::{CandidateDisplay, PencilMark};
+use crate::ui::SudokuUi;
+use eframe::egui;
+use egui::{Key, Modifiers};
+
+const DIGIT_KEYS: [Key; 9] = [
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+];
+
+/// What Shift and the digit keys type on a US keyboard. egui has no key for these characters, so
+/// such a press only arrives as text.
+const SHIFTED_DIGITS: [&str; 9] = ["!", "@", "#", "$", "%", "^", "&", "*", "("];
+
+/// The fields the keyboard input goes to.
+#[derive(Clone, Debug, Default)]
+pub struct Selection {
+    cursor: Option<FieldPosition>,
+    fields: Vec<FieldPosition>,
+    /// Digits toggle candidates or corner marks instead of placing values, like holding Shift.
+    pencil_mode: bool,
+}
+
+impl Selection {
+    pub fn fields(&self) -> &[FieldPosition] {
+        &self.fields
+    }
+
+    /// Selects only the given field. With Ctrl it is toggled, with Shift it is added to the
+    /// selection instead.
+    pub fn select(&mut self, position: FieldPosition, modifiers: Modifiers) {
+        if modifiers.command {
+            match self.fields.iter().position(|p| *p == position) {
+                Some(index) => {
+                    self.fields.remove(index);
+                }
+                None => self.fields.push(position.clone()),
+            }
+        } else if modifiers.shift {
+            if !self.fields.contains(&position) {
+                self.fields.push(position.clone());
+            }
+        } else {
+            self.fields = vec![position.clone()];
+        }
+
+        self.cursor = Some(position);
+    }
+
+    /// Moves the cursor, keeping the fields it passes selected if Ctrl or Shift is held.
+    fn move_cursor(&mut self, rows: isize, columns: isize, modifiers: Modifiers) {
+        let position = match &self.cursor {
+            None => FieldPosition::new(0, 0),
+            Some(cursor) => FieldPosition::new(
+                cursor.row().saturating_add_signed(rows).min(8),
+                cursor.column().saturating_add_signed(columns).min(8),
+            ),
+        };
+
+        if modifiers.command || modifiers.shift {
+            if !self.fields.contains(&position) {
+                self.fields.push(position.clone());
+            }
+            self.cursor = Some(position);
+        } else {
+            self.select(position, Modifiers::NONE);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.cursor = None;
+        self.fields.clear();
+    }
+}
+
+impl SudokuUi {
+    /// Moves the selection with the arrow keys or WASD, places digits and clears fields with
+    /// Delete. Shift toggles candidates, or corner marks while pencil marks are shown, Ctrl
+    /// always toggles centre marks. Shift is only recognized on keyboards that type a digit or
+    /// the US symbol for it, pencil mode works with any layout.
+    pub fn keyboard_input(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let (events, modifiers) = ctx.input(|i| (i.events.clone(), i.modifiers));
+        for event in events {
+            match event {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } => self.key_input(key, modifiers),
+                egui::Event::Text(text) if modifiers.shift => {
+                    if let Some(digit) = SHIFTED_DIGITS.iter().position(|s| *s == text) {
+                        self.digit_input(digit + 1, modifiers);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn key_input(&mut self, key: Key, modifiers: Modifiers) {
+        match key {
+            Key::ArrowUp | Key::W => self.selection.move_cursor(-1, 0, modifiers),
+            Key::ArrowDown | Key::S => self.selection.move_cursor(1, 0, modifiers),
+            Key::ArrowLeft | Key::A => self.selection.move_cursor(0, -1, modifiers),
+            Key::ArrowRight | Key::D => self.selection.move_cursor(0, 1, modifiers),
+            Key::Delete | Key::Backspace => self.clear_selected_values(),
+            Key::Escape => self.selection.clear(),
+            _ => {
+                if let Some(digit) = DIGIT_KEYS.iter().position(|k| *k == key) {
+                    self.digit_input(digit + 1, modifiers);
+                }
+            }
+        }
+    }
+
+    fn digit_input(&mut self, digit: usize, modifiers: Modifiers) {
+        let pencil = modifiers.shift || self.selection.pencil_mode;
+        if modifiers.command {
+            self.toggle_selected_pencil_mark(digit, PencilMark::Centre);
+        } else if pencil {
+            match self.candidate_display {
+                CandidateDisplay::Solver => self.toggle_selected_possibility(digit),
+                CandidateDisplay::PencilMarks => {
+                    self.toggle_selected_pencil_mark(digit, PencilMark::Corner)
+                }
+            }
+        } else {
+            self.place_selected_value(digit);
+        }
+    }
+
+    pub fn keyboard_ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.selection.pencil_mode, "Pencil mode");
+    }
+
+    pub(super) fn selected_fields(&self) -> Vec<&Field> {
+        let Some(grid) = self.grid() else {
+            return vec![];
+        };
+
+        self.selection
+            .fields()
+            .iter()
+            .filter_map(|p| grid.grid.get_field(p.clone()))
+            .collect()
+    }
+
+    /// Whether the field was filled from the start, those can not be changed.
+    fn is_given(&self, position: &FieldPosition) -> bool {
+        self.initial_grid
+            .as_ref()
+            .and_then(|grid| grid.get_field(position.clone()))
+            .is_some_and(|f| f.is_filled())
+    }
+
+    fn place_selected_value(&mut self, digit: usize) {
+        let changes = self
+            .selected_fields()
+            .into_iter()
+            .filter(|f| !self.is_given(f.position()) && f.value() != Some(digit))
+            .map(|f| (f.position().clone(), ManualChange::SetValue(digit)))
+            .collect();
+
+        self.apply_manual_changes(changes);
+    }
+
+    /// Removes the candidate from the selected fields if all of them have it, adds it to the
+    /// others otherwise.
+    fn toggle_selected_possibility(&mut self, digit: usize) {
+        let fields = self
+            .selected_fields()
+            .into_iter()
+            .filter(|f| !f.is_filled())
+            .collect::<Vec<&Field>>();
+        let remove = fields.iter().all(|f| f.possibilities().contains(digit));
+
+        let changes = fields
+            .into_iter()
+            .filter(|f| f.possibilities().contains(digit) == remove)
+            .map(|f| {
+                let change = match remove {
+                    true => ManualChange::RemovePossibility(digit),
+                    false => ManualChange::AddPossibility(digit),
+                };
+                (f.position().clone(), change)
+            })
+            .collect();
+
+        self.apply_manual_changes(changes);
+    }
+
+    fn clear_selected_values(&mut self) {
+        let changes = self
+            .selected_fields()
+            .into_iter()
+            .filter(|f| f.is_filled() && !self.is_given(f.position()))
+            .map(|f| (f.position().clone(), ManualChange::ClearValue))
+            .collect();
+
+        self.apply_manual_changes(changes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::solver::{SolutionCount, SudokuSolver};
+    use crate::sudoku::candidates::Candidates;
+    use crate::sudoku::field::FieldPosition;
+    use crate::sudoku::grid::SudokuGrid;
+    use crate::ui::history::HistoryEntry;
+    use crate::ui::pencil_marks::CandidateDisplay;
+    use crate::ui::SudokuUi;
+    use eframe::egui::{self, Event, Key, Modifiers, RawInput};
+
+    /// Runs a frame with the input the way egui-winit delivers it.
+    fn input(ui: &mut SudokuUi, modifiers: Modifiers, events: Vec<Event>) {
+        let ctx = egui::Context::default();
+        // the first frame gains the focus, which resets the modifiers
+        let _ = ctx.run(RawInput::default(), |_| ());
+        let input = RawInput {
+            modifiers,
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| ui.keyboard_input(ctx));
+    }
+
+    fn grid8_with_selected_field() -> (SudokuUi, FieldPosition) {
+        let grid = SudokuGrid::try_from(PathBuf::from("data/grid8.txt")).unwrap();
+        let position = grid
+            .fields()
+            .into_iter()
+            .find(|f| !f.is_filled())
+            .unwrap()
+            .position()
+            .clone();
+        let mut ui = SudokuUi::new(Some(grid));
+        ui.selection.select(position.clone(), Modifiers::NONE);
+
+        (ui, position)
+    }
+
+    #[test]
+    fn it_toggles_candidates_with_shift_and_a_digit() {
+        let (mut ui, position) = grid8_with_selected_field();
+        let digit = ui
+            .grid()
+            .unwrap()
+            .grid
+            .get_field(position.clone())
+            .unwrap()
+            .possibilities()
+            .first()
+            .unwrap();
+
+        // Shift+digit has no key event, only the typed symbol
+        input(
+            &mut ui,
+            Modifiers::SHIFT,
+            vec![Event::Text(super::SHIFTED_DIGITS[digit - 1].to_string())],
+        );
+
+        let field = ui.grid().unwrap().grid.get_field(position).unwrap().clone();
+        assert!(!field.is_filled());
+        assert!(!field.possibilities().contains(digit));
+    }
+
+    #[test]
+    fn it_toggles_pencil_marks_with_shift_or_ctrl_and_a_digit() {
+        let (mut ui, position) = grid8_with_selected_field();
+        ui.candidate_display = CandidateDisplay::PencilMarks;
+
+        input(
+            &mut ui,
+            Modifiers::SHIFT,
+            vec![Event::Text("!".to_string())],
+        );
+        // Ctrl keeps the digit, so it arrives as a key, with no text
+        input(
+            &mut ui,
+            Modifiers::COMMAND,
+            vec![Event::Key {
+                key: Key::Num2,
+                physical_key: Some(Key::Num2),
+                pressed: true,
+                repeat: false,
+                modifiers: Modifiers::COMMAND,
+            }],
+        );
+
+        let pencil_marks = ui.grid().unwrap().pencil_marks(&position);
+        assert_eq!(Candidates::single(1), pencil_marks.corner);
+        assert_eq!(Candidates::single(2), pencil_marks.centre);
+    }
+
+    #[test]
+    fn it_brings_back_the_candidates_of_a_cleared_value() {
+        let grid = SudokuGrid::try_from(PathBuf::from("data/grid8.txt")).unwrap();
+        let solution = SudokuSolver::new().find_solutions(&grid, 1).remove(0);
+        let mut ui = SudokuUi::new(Some(grid.clone()));
+
+        let field = grid.fields().into_iter().find(|f| !f.is_filled()).unwrap();
+        let position = field.position().clone();
+        let correct = solution
+            .get_field(position.clone())
+            .unwrap()
+            .value()
+            .unwrap();
+        let peer_values = grid
+            .get_peers(&position)
+            .into_iter()
+            .filter_map(|f| f.value())
+            .collect::<Vec<usize>>();
+        let wrong = (1..=9)
+            .find(|d| *d != correct && !peer_values.contains(d))
+            .unwrap();
+
+        ui.selection.select(position.clone(), Modifiers::NONE);
+        ui.place_selected_value(wrong);
+        assert_eq!(Some(SolutionCount::None), ui.solution_count);
+
+        // the row, column and box eliminations take the wrong value out of the peers
+        for _ in 0..3 {
+            let current = ui.grid().unwrap().grid.clone();
+            for deduction in ui.solver.determine_deductions(&current) {
+                ui.add_history_entry(HistoryEntry::Solver(deduction));
+            }
+        }
+        assert!(ui
+            .grid()
+            .unwrap()
+            .grid
+            .get_peers(&position)
+            .into_iter()
+            .all(|f| !f.possibilities().contains(wrong)));
+
+        ui.clear_selected_values();
+
+        assert_eq!(Some(SolutionCount::Unique), ui.solution_count);
+        let cleared = ui.grid().unwrap().grid.get_field(position).unwrap().clone();
+        assert!(cleared.possibilities().contains(correct));
+    }
+}
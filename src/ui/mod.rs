@@ -1,20 +1,54 @@
 use std::env::current_dir;
 
-use crate::solver::{SolveStep, SudokuSolver};
+use crate::generator::GeneratorOptions;
+use crate::solver::grader::{Grader, Grading};
+use crate::solver::{SolutionCount, SudokuSolver};
 use crate::sudoku::field::{Field, FieldPosition};
 use crate::sudoku::grid::SudokuGrid;
+use crate::sudoku::validation::Validity;
 use eframe::{egui, App};
-use egui::Color32;
+use egui::{Color32, Rgba};
 use egui_extras::{Size, Strip, StripBuilder};
 
 mod export;
+mod generate;
+mod hint;
+mod history;
 mod import;
+mod keyboard;
+mod pencil_marks;
+mod playback;
+
+use export::ExportFormat;
+use generate::Generation;
+use hint::Hint;
+use history::{HistoryEntry, ManualChange};
+use keyboard::Selection;
+use pencil_marks::{CandidateDisplay, PencilMarks};
+use playback::Playback;
 
 pub struct SudokuUi {
-    auto_solve: bool,
+    playback: Playback,
+    /// Only uses the uniqueness techniques while the grid has a single solution, see
+    /// `update_analysis`.
     solver: SudokuSolver,
+    /// Whether the player allows the uniqueness techniques at all.
+    uniqueness_techniques: bool,
+    /// The grid as it was imported or generated, the history is replayed on top of it.
+    initial_grid: Option<SudokuGrid>,
     grid: Option<SudokuGridWithColoredFields>,
-    solve_steps: Vec<(FieldPosition, SolveStep)>,
+    history: Vec<HistoryEntry>,
+    /// How many steps of the history are applied to the grid, less than all after a rewind.
+    applied_steps: usize,
+    solution_count: Option<SolutionCount>,
+    grading: Option<Grading>,
+    generator_options: GeneratorOptions,
+    generation: Option<Generation>,
+    hint: Option<Hint>,
+    selection: Selection,
+    candidate_display: CandidateDisplay,
+    /// What went wrong with the last action from the menu.
+    error: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -38,6 +72,46 @@ impl SudokuGridWithColoredFields {
                 .collect(),
         }
     }
+
+    /// Colors the given fields on top of their own color and clears every other highlight.
+    fn set_highlights(&mut self, highlights: &[(FieldPosition, Color32)]) {
+        for field_metadata in self.field_metadata.iter_mut() {
+            field_metadata.metadata.highlight = highlights
+                .iter()
+                .rev()
+                .find(|(p, _)| *p == field_metadata.field_position)
+                .map(|(_, color)| *color);
+        }
+    }
+
+    /// Marks the fields of every conflict in the grid.
+    fn update_conflicts(&mut self) {
+        let conflicts = self.grid.find_conflicts().positions();
+        for field_metadata in self.field_metadata.iter_mut() {
+            field_metadata.metadata.conflict = conflicts.contains(&field_metadata.field_position);
+        }
+    }
+
+    fn set_selection(&mut self, selection: &[FieldPosition]) {
+        for field_metadata in self.field_metadata.iter_mut() {
+            field_metadata.metadata.selected = selection.contains(&field_metadata.field_position);
+        }
+    }
+
+    fn metadata_mut(&mut self, position: &FieldPosition) -> Option<&mut FieldMetaData> {
+        self.field_metadata
+            .iter_mut()
+            .find(|f| f.field_position == *position)
+            .map(|f| &mut f.metadata)
+    }
+
+    fn pencil_marks(&self, position: &FieldPosition) -> PencilMarks {
+        self.field_metadata
+            .iter()
+            .find(|f| f.field_position == *position)
+            .map(|f| f.metadata.pencil_marks)
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -58,16 +132,35 @@ impl FieldWithMetaData {
 #[derive(Clone, Copy, Debug, Default)]
 pub struct FieldMetaData {
     color: Option<Color32>,
+    highlight: Option<Color32>,
+    selected: bool,
+    conflict: bool,
+    pencil_marks: PencilMarks,
 }
 
 impl SudokuUi {
     pub fn new(grid: Option<SudokuGrid>) -> Self {
-        Self {
-            auto_solve: false,
+        let mut sudoku_ui = Self {
+            playback: Playback::default(),
             solver: SudokuSolver::new(),
+            uniqueness_techniques: true,
+            initial_grid: grid.clone(),
             grid: grid.map(SudokuGridWithColoredFields::new),
-            solve_steps: vec![],
-        }
+            history: vec![],
+            applied_steps: 0,
+            solution_count: None,
+            grading: None,
+            generator_options: GeneratorOptions::default(),
+            generation: None,
+            hint: None,
+            selection: Selection::default(),
+            candidate_display: CandidateDisplay::default(),
+            error: None,
+        };
+        sudoku_ui.grade_puzzle();
+        sudoku_ui.update_analysis();
+
+        sudoku_ui
     }
 
     pub fn grid(&self) -> Option<&SudokuGridWithColoredFields> {
@@ -78,29 +171,63 @@ impl SudokuUi {
         &mut self.grid
     }
 
-    pub fn add_solve_steps(&mut self, solve_steps: &Vec<(FieldPosition, SolveStep)>) {
-        for (position, solve_step) in solve_steps {
-            self.solve_steps
-                .push((position.clone(), solve_step.clone()));
+    /// Starts over with a new puzzle, dropping the history.
+    pub fn set_grid(&mut self, grid: SudokuGrid) {
+        self.initial_grid = Some(grid.clone());
+        self.grid = Some(SudokuGridWithColoredFields::new(grid));
+        self.history.clear();
+        self.applied_steps = 0;
+        self.hint = None;
+        self.error = None;
+        self.grade_puzzle();
+        self.update_analysis();
+    }
+
+    /// Rates the puzzle as it was imported or generated. The progress of the player does not
+    /// change how hard the puzzle is, so this is only needed once per puzzle and whenever the
+    /// uniqueness techniques are switched. Those are only used if the puzzle is unique.
+    pub fn grade_puzzle(&mut self) {
+        self.grading = self.initial_grid.as_ref().and_then(|grid| {
+            let uniqueness = self.uniqueness_techniques
+                && self.solver.count_solutions(grid) == SolutionCount::Unique;
+            let solver = SudokuSolver::new().with_uniqueness_strategies(uniqueness);
+
+            Grader::with_solver(solver).grade(grid)
+        });
+    }
+
+    /// Determines the number of solutions of the current grid, needed after every change to it.
+    /// Hints and playback only use the uniqueness techniques while there is a single one.
+    pub fn update_analysis(&mut self) {
+        self.solution_count = self
+            .grid()
+            .map(|grid| self.solver.count_solutions(&grid.grid));
+        self.solver.set_uniqueness_strategies(
+            self.uniqueness_techniques && self.solution_count == Some(SolutionCount::Unique),
+        );
+    }
+
+    /// Shows the error next to the grid until the player dismisses it.
+    pub fn show_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Applies changes the player made by hand as a single step of the history.
+    pub fn apply_manual_changes(&mut self, changes: Vec<(FieldPosition, ManualChange)>) {
+        if changes.is_empty() {
+            return;
         }
+
+        self.add_history_entry(HistoryEntry::Manual(changes));
     }
 }
 
 impl App for SudokuUi {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if self.auto_solve {
-            if let Some(grid) = self.grid() {
-                if !grid.grid.is_completed() {
-                    let solve_steps = self.solver.determine_solve_steps(&grid.grid);
-
-                    if let Some(mut_grid) = self.grid_mut() {
-                        mut_grid.grid.apply_solve_steps(&solve_steps);
-
-                        self.add_solve_steps(&solve_steps);
-                    }
-                }
-            }
-        }
+        self.history_shortcuts(ctx);
+        self.keyboard_input(ctx);
+        self.play();
+        self.receive_puzzle();
 
         egui::CentralPanel::default().show(ctx, |ui| {
             StripBuilder::new(ui)
@@ -109,29 +236,62 @@ impl App for SudokuUi {
                 .vertical(|mut vertical_strip| {
                     vertical_strip.cell(|menu_ui| {
                         menu_ui.horizontal(|menu_ui| {
-                            if menu_ui.button("Export").clicked() {
-                                if let Ok(cwd) = current_dir() {
-                                    let fd = rfd::FileDialog::new();
-                                    if let Some(path) = fd.set_directory(cwd).pick_file() {
-                                        self.export_to(&path);
+                            menu_ui.menu_button("Export", |export_ui| {
+                                for format in ExportFormat::ALL {
+                                    if export_ui.button(format.to_string()).clicked() {
+                                        export_ui.close_menu();
+                                        if let Ok(cwd) = current_dir() {
+                                            let fd = rfd::FileDialog::new();
+                                            if let Some(path) = fd.set_directory(cwd).pick_file() {
+                                                self.export_to(&path, format);
+                                            }
+                                        }
                                     }
                                 }
-                            }
+                            });
 
                             if menu_ui.button("Import").clicked() {
                                 if let Ok(cwd) = current_dir() {
                                     let fd = rfd::FileDialog::new();
                                     if let Some(path) = fd.set_directory(cwd).pick_file() {
-                                        self.solve_steps.clear();
-                                        let result = self.import_from(&path);
-                                        if result.is_err() {
-                                            menu_ui.label("That didn't work");
+                                        if let Err(error) = self.import_from(&path) {
+                                            self.error = Some(format!(
+                                                "Could not import {}: {error}",
+                                                path.display()
+                                            ));
                                         }
                                     }
                                 }
                             }
 
-                            menu_ui.checkbox(&mut self.auto_solve, "Auto solve");
+                            self.generate_ui(menu_ui);
+
+                            menu_ui.menu_button("Puzzle options", |options_ui| {
+                                self.generator_options_ui(options_ui);
+                            });
+
+                            if menu_ui.button("Hint").clicked() {
+                                if let Err(error) = self.hint() {
+                                    self.error = Some(error);
+                                }
+                            }
+
+                            if menu_ui
+                                .checkbox(&mut self.uniqueness_techniques, "Uniqueness techniques")
+                                .changed()
+                            {
+                                self.hint = None;
+                                self.grade_puzzle();
+                                self.update_analysis();
+                            }
+
+                            self.history_ui(menu_ui);
+                            self.playback_ui(menu_ui);
+                            self.keyboard_ui(menu_ui);
+
+                            menu_ui.menu_button("Candidates", |candidates_ui| {
+                                self.pencil_marks_ui(candidates_ui);
+                            });
                         });
                     });
 
@@ -142,42 +302,81 @@ impl App for SudokuUi {
                             .horizontal(|mut horizontal_strip| {
                                 horizontal_strip.cell(|ui| {
                                     let mut changes = vec![];
+                                    let mut clicked = None;
+                                    let highlights = self
+                                        .hint
+                                        .as_ref()
+                                        .map(Hint::highlights)
+                                        .unwrap_or_default();
+                                    let selection = self.selection.fields().to_vec();
+                                    let display = self.candidate_display;
                                     match self.grid_mut() {
                                         None => (),
                                         Some(grid) => {
-                                            changes = grid.ui(ui);
+                                            grid.set_highlights(&highlights);
+                                            grid.set_selection(&selection);
+                                            grid.update_conflicts();
+                                            (changes, clicked) = grid.ui(ui, display);
                                         }
                                     }
 
-                                    if let Some(grid) = self.grid_mut() {
-                                        grid.grid.apply_solve_steps(&changes);
-
-                                        self.add_solve_steps(&changes);
+                                    if let Some(position) = clicked {
+                                        let modifiers = ui.input(|i| i.modifiers);
+                                        self.selection.select(position, modifiers);
                                     }
+                                    self.apply_manual_changes(changes);
                                 });
 
                                 horizontal_strip.cell(|h_ui| {
                                     egui::ScrollArea::vertical().show(h_ui, |scroll_ui| {
+                                        if let Some(error) = &self.error {
+                                            scroll_ui.colored_label(Color32::RED, error);
+                                            if scroll_ui.button("Dismiss").clicked() {
+                                                self.error = None;
+                                            }
+                                            scroll_ui.separator();
+                                        }
+
+                                        self.hint_ui(scroll_ui);
+
+                                        if let Some(solution_count) = self.solution_count {
+                                            scroll_ui.label(solution_count.to_string());
+                                        }
+
+                                        if let Some(grading) = &self.grading {
+                                            scroll_ui.label(format!("Difficulty {grading}"));
+                                        }
+
                                         if let Some(grid) = self.grid() {
-                                            if grid.grid.is_completed() {
-                                                scroll_ui.label("You won!");
+                                            match grid.grid.validate() {
+                                                Validity::Solved => {
+                                                    scroll_ui.label("You won!");
+                                                }
+                                                Validity::CompleteWithErrors => {
+                                                    scroll_ui.label(
+                                                        "Every field is filled, but some are wrong",
+                                                    );
+                                                }
+                                                Validity::Incomplete => (),
                                             }
                                         }
 
-                                        for (position, solve_step) in self.solve_steps.iter().rev()
+                                        let mut rewind_to = None;
+                                        for (index, entry) in self.history.iter().enumerate().rev()
                                         {
-                                            scroll_ui.label(format!(
-                                                "{} / {} => {}",
-                                                position.row(),
-                                                position.column(),
-                                                match &solve_step {
-                                                    SolveStep::SetValue(value) =>
-                                                        format!("Set {value}"),
-                                                    SolveStep::RemovePossibilities(p) => {
-                                                        format!("Remove {p:?}")
-                                                    }
-                                                },
-                                            ));
+                                            let mut text = egui::RichText::new(entry.to_string());
+                                            if index >= self.applied_steps {
+                                                text = text.weak();
+                                            }
+
+                                            let current = index + 1 == self.applied_steps;
+                                            if scroll_ui.selectable_label(current, text).clicked() {
+                                                rewind_to = Some(index + 1);
+                                            }
+                                        }
+
+                                        if let Some(applied_steps) = rewind_to {
+                                            self.rewind_to(applied_steps);
                                         }
                                     });
                                 });
@@ -191,38 +390,60 @@ impl App for SudokuUi {
 }
 
 impl SudokuGridWithColoredFields {
-    fn ui(&mut self, ui: &mut egui::Ui) -> Vec<(FieldPosition, SolveStep)> {
+    /// Draws the grid, returns the changes made by hand and the field clicked to be selected.
+    fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        display: CandidateDisplay,
+    ) -> (Vec<(FieldPosition, ManualChange)>, Option<FieldPosition>) {
         let mut changes = vec![];
+        let mut clicked = None;
         draw_grid(ui, 9, 9, |field_strip, position| {
             field_strip.cell(|ui| {
                 let index = position.row() * 9 + position.column();
                 if let Some(field_metadata) = self.field_metadata.get_mut(index) {
                     if let Some(field) = self.grid.get_field(field_metadata.field_position.clone())
                     {
-                        let p = field_metadata.ui(ui, field);
-                        if let Some(solve_step) = p {
-                            changes.push((field_metadata.field_position.clone(), solve_step));
+                        let (change, field_clicked) = field_metadata.ui(ui, field, display);
+                        if let Some(change) = change {
+                            changes.push((field_metadata.field_position.clone(), change));
+                        }
+                        if field_clicked {
+                            clicked = Some(field_metadata.field_position.clone());
                         }
                     }
                 }
             });
         });
 
-        changes
+        (changes, clicked)
     }
 }
 
 impl FieldWithMetaData {
-    fn ui(&mut self, ui: &mut egui::Ui, field: &Field) -> Option<SolveStep> {
+    /// Draws the field, returns the change made by hand and whether the field was clicked.
+    fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        field: &Field,
+        display: CandidateDisplay,
+    ) -> (Option<ManualChange>, bool) {
+        let rect = ui.available_rect_before_wrap();
+        let id = egui::Id::new((
+            "field",
+            self.field_position.row(),
+            self.field_position.column(),
+        ));
+        let clicked = ui.interact(rect, id, egui::Sense::click()).clicked();
+
         let dark_mode = ui.visuals().dark_mode;
-        let faded_color = ui.visuals().window_fill();
+        let window_fill = ui.visuals().window_fill();
         let faded_color = |color: Color32| -> Color32 {
-            use egui::Rgba;
             let t = if dark_mode { 0.95 } else { 0.8 };
-            egui::lerp(Rgba::from(color)..=Rgba::from(faded_color), t).into()
+            egui::lerp(Rgba::from(color)..=Rgba::from(window_fill), t).into()
         };
 
-        let mut solve_step: Option<SolveStep> = None;
+        let mut change: Option<ManualChange> = None;
         self.metadata.color = match field.value().unwrap_or(0) {
             1 => Some(Color32::BLUE),
             2 => Some(Color32::GREEN),
@@ -236,17 +457,21 @@ impl FieldWithMetaData {
             _ => None,
         };
 
-        let color = faded_color(self.metadata.color.unwrap_or(Color32::WHITE));
+        let color = match self.metadata.highlight {
+            _ if self.metadata.conflict => {
+                egui::lerp(Rgba::from(Color32::RED)..=Rgba::from(window_fill), 0.3).into()
+            }
+            Some(highlight) => {
+                egui::lerp(Rgba::from(highlight)..=Rgba::from(window_fill), 0.5).into()
+            }
+            None => faded_color(self.metadata.color.unwrap_or(Color32::WHITE)),
+        };
         match field.value() {
+            None if display == CandidateDisplay::PencilMarks => {
+                ui.painter().rect_filled(rect, 0.0, color);
+                pencil_marks::draw_pencil_marks(ui, rect, self.metadata.pencil_marks);
+            }
             None => {
-                if field.possibilities().is_empty() {
-                    panic!(
-                        "No field value nor possibilities exist for {} / {}",
-                        field.position().row(),
-                        field.position().column()
-                    );
-                }
-
                 ui.painter()
                     .rect_filled(ui.available_rect_before_wrap(), 0.0, color);
 
@@ -254,8 +479,8 @@ impl FieldWithMetaData {
                     field_strip.cell(|ui| {
                         let possibility = position.row() * 3 + position.column() + 1;
                         ui.centered_and_justified(|ui| {
-                            if field.possibilities().contains(&possibility) {
-                                let response = ui.label(&possibility.to_string());
+                            if field.possibilities().contains(possibility) {
+                                let response = ui.label(possibility.to_string());
 
                                 let response = response.interact(egui::Sense::click());
                                 if response.clicked() {
@@ -272,11 +497,10 @@ impl FieldWithMetaData {
                                         _ => Color32::BLACK,
                                     });
 
-                                    solve_step = Some(SolveStep::SetValue(possibility));
+                                    change = Some(ManualChange::SetValue(possibility));
                                 }
                                 response.context_menu(|ui| {
-                                    solve_step =
-                                        Some(SolveStep::RemovePossibilities(vec![possibility]));
+                                    change = Some(ManualChange::RemovePossibility(possibility));
                                     ui.close_menu();
                                 });
                             } else {
@@ -296,7 +520,12 @@ impl FieldWithMetaData {
             }
         }
 
-        solve_step
+        if self.metadata.selected {
+            let stroke = egui::Stroke::new(3.0, ui.visuals().selection.stroke.color);
+            ui.painter().rect_stroke(rect.shrink(1.5), 0.0, stroke);
+        }
+
+        (change, clicked)
     }
 }
 
@@ -0,0 +1,172 @@
+use std::fmt;
+
+use crate::sudoku::candidates::Candidates;
+use crate::sudoku::field::Field;
+use crate::ui::history::ManualChange;
+use crate::ui::SudokuUi;
+use eframe::egui;
+use egui::{Align2, FontId, Rect};
+
+/// What empty fields show: the notes of the player or the candidates of the solver.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CandidateDisplay {
+    #[default]
+    Solver,
+    PencilMarks,
+}
+
+impl CandidateDisplay {
+    pub const ALL: [CandidateDisplay; 2] =
+        [CandidateDisplay::Solver, CandidateDisplay::PencilMarks];
+}
+
+impl fmt::Display for CandidateDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CandidateDisplay::Solver => write!(f, "Solver candidates"),
+            CandidateDisplay::PencilMarks => write!(f, "Pencil marks"),
+        }
+    }
+}
+
+/// Notes of the player, kept apart from the candidates the solver works with.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PencilMarks {
+    /// Digits that can only go into a few fields of a house, written into the corners.
+    pub corner: Candidates,
+    /// Digits the field may hold, written into the centre.
+    pub centre: Candidates,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PencilMark {
+    Corner,
+    Centre,
+}
+
+impl SudokuUi {
+    /// Notes every digit none of its peers holds in the centre of each empty field.
+    pub fn auto_fill_pencil_marks(&mut self) {
+        let Some(grid) = self.grid() else {
+            return;
+        };
+
+        let changes = grid
+            .grid
+            .fields()
+            .into_iter()
+            .filter(|f| !f.is_filled())
+            .map(|f| {
+                let values = grid
+                    .grid
+                    .get_peers(f.position())
+                    .into_iter()
+                    .filter_map(|peer| peer.value())
+                    .collect::<Candidates>();
+                let pencil_marks = PencilMarks {
+                    centre: Candidates::all() - values,
+                    ..grid.pencil_marks(f.position())
+                };
+                (f.position().clone(), pencil_marks)
+            })
+            .filter(|(position, pencil_marks)| grid.pencil_marks(position) != *pencil_marks)
+            .map(|(position, pencil_marks)| (position, ManualChange::SetPencilMarks(pencil_marks)))
+            .collect();
+
+        self.apply_manual_changes(changes);
+    }
+
+    /// Removes the mark from the selected empty fields if all of them have it, adds it to the
+    /// others otherwise.
+    pub fn toggle_selected_pencil_mark(&mut self, digit: usize, mark: PencilMark) {
+        let Some(grid) = self.grid() else {
+            return;
+        };
+        let marks = |field: &Field| match mark {
+            PencilMark::Corner => grid.pencil_marks(field.position()).corner,
+            PencilMark::Centre => grid.pencil_marks(field.position()).centre,
+        };
+
+        let fields = self
+            .selected_fields()
+            .into_iter()
+            .filter(|f| !f.is_filled())
+            .collect::<Vec<&Field>>();
+        let remove = fields.iter().all(|f| marks(f).contains(digit));
+
+        let changes = fields
+            .into_iter()
+            .filter(|f| marks(f).contains(digit) == remove)
+            .map(|f| {
+                let mut pencil_marks = grid.pencil_marks(f.position());
+                let toggled = match mark {
+                    PencilMark::Corner => &mut pencil_marks.corner,
+                    PencilMark::Centre => &mut pencil_marks.centre,
+                };
+                match remove {
+                    true => toggled.remove(digit),
+                    false => toggled.insert(digit),
+                }
+                (
+                    f.position().clone(),
+                    ManualChange::SetPencilMarks(pencil_marks),
+                )
+            })
+            .collect();
+
+        self.apply_manual_changes(changes);
+    }
+
+    pub fn pencil_marks_ui(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("Show")
+            .selected_text(self.candidate_display.to_string())
+            .show_ui(ui, |ui| {
+                for display in CandidateDisplay::ALL {
+                    ui.selectable_value(&mut self.candidate_display, display, display.to_string());
+                }
+            });
+
+        if ui.button("Auto-fill candidates").clicked() {
+            self.auto_fill_pencil_marks();
+        }
+    }
+}
+
+/// Writes the corner marks along the edges, starting in the corners, and the centre marks into
+/// the middle of the field.
+pub fn draw_pencil_marks(ui: &egui::Ui, rect: Rect, pencil_marks: PencilMarks) {
+    let font = FontId::proportional(rect.height() / 5.0);
+    let color = ui.visuals().text_color();
+    let inner = rect.shrink(rect.height() / 10.0);
+
+    let anchors = [
+        Align2::LEFT_TOP,
+        Align2::RIGHT_TOP,
+        Align2::LEFT_BOTTOM,
+        Align2::RIGHT_BOTTOM,
+        Align2::CENTER_TOP,
+        Align2::CENTER_BOTTOM,
+        Align2::LEFT_CENTER,
+        Align2::RIGHT_CENTER,
+        Align2::CENTER_CENTER,
+    ];
+    for (digit, anchor) in pencil_marks.corner.iter().zip(anchors) {
+        ui.painter().text(
+            anchor.pos_in_rect(&inner),
+            anchor,
+            digit.to_string(),
+            font.clone(),
+            color,
+        );
+    }
+
+    if !pencil_marks.centre.is_empty() {
+        let text = pencil_marks
+            .centre
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<String>();
+        ui.painter()
+            .text(rect.center(), Align2::CENTER_CENTER, text, font, color);
+    }
+}
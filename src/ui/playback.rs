@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+use crate::sudoku::validation::Validity;
+use crate::ui::history::HistoryEntry;
+use crate::ui::SudokuUi;
+use eframe::egui;
+
+/// Replays the recorded steps and asks the solver for new ones once they run out.
+pub struct Playback {
+    playing: bool,
+    steps_per_second: f32,
+    last_step: Instant,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            playing: false,
+            steps_per_second: 2.0,
+            last_step: Instant::now(),
+        }
+    }
+}
+
+impl SudokuUi {
+    /// Redoes the next recorded step or, if there is none, applies the easiest step the solver
+    /// finds. Returns whether the grid changed.
+    pub fn step_forward(&mut self) -> bool {
+        if self.redo() {
+            return true;
+        }
+
+        let Some(grid) = self.grid() else {
+            return false;
+        };
+        if grid.grid.validate() != Validity::Incomplete {
+            return false;
+        }
+        // unlike a hint, playing may guess once the strategies are stuck
+        let Some(deduction) = self
+            .solver
+            .determine_deductions(&grid.grid)
+            .into_iter()
+            .next()
+        else {
+            return false;
+        };
+
+        self.add_history_entry(HistoryEntry::Solver(deduction));
+
+        true
+    }
+
+    pub fn pause(&mut self) {
+        self.playback.playing = false;
+    }
+
+    /// Makes the next step once it is due, stops playing when the solver gets stuck.
+    pub fn play(&mut self) {
+        if !self.playback.playing {
+            return;
+        }
+
+        let interval = Duration::from_secs_f32(1.0 / self.playback.steps_per_second);
+        if self.playback.last_step.elapsed() < interval {
+            return;
+        }
+
+        self.playback.last_step = Instant::now();
+        if !self.step_forward() {
+            self.playback.playing = false;
+        }
+    }
+
+    pub fn playback_ui(&mut self, ui: &mut egui::Ui) {
+        if ui.button("Rewind").clicked() {
+            self.rewind_to(0);
+        }
+
+        if ui.button("Step").clicked() {
+            self.playback.playing = false;
+            self.step_forward();
+        }
+
+        let label = if self.playback.playing {
+            "Pause"
+        } else {
+            "Play"
+        };
+        if ui.button(label).clicked() {
+            self.playback.playing = !self.playback.playing;
+        }
+
+        ui.add(
+            egui::Slider::new(&mut self.playback.steps_per_second, 1.0..=30.0)
+                .text("Steps per second"),
+        );
+    }
+}